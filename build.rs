@@ -0,0 +1,94 @@
+// Decides whether the macros pass their input through unchanged, which is the case when
+// the active toolchain accepts the nightly syntax as is.
+//
+// UNCONST_TRAIT_IMPL_MODE=passthrough or UNCONST_TRAIT_IMPL_MODE=unconst forces either mode.
+// Otherwise, the toolchain is probed. Nothing but the active rustc is run, so the probe
+// works offline.
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+const MODE_VAR: &str = "UNCONST_TRAIT_IMPL_MODE";
+
+const PROBE: &str = r#"
+#![feature(const_trait_impl)]
+pub const trait Probe {
+    fn probe() -> u8;
+}
+pub struct Unit;
+impl const Probe for Unit {
+    fn probe() -> u8 {
+        0
+    }
+}
+"#;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed={}", MODE_VAR);
+    println!("cargo:rustc-check-cfg=cfg(unconst_trait_impl_passthrough)");
+
+    let passthrough = match env::var(MODE_VAR) {
+        Ok(mode) if mode == "passthrough" => true,
+        Ok(mode) if mode == "unconst" => false,
+        Ok(mode) => panic!(
+            "{} must be either `passthrough` or `unconst`, not `{}`",
+            MODE_VAR, mode
+        ),
+        Err(_) => probe(),
+    };
+    if passthrough {
+        println!("cargo:rustc-cfg=unconst_trait_impl_passthrough");
+    }
+}
+
+fn rustc() -> OsString {
+    env::var_os("RUSTC").unwrap_or_else(|| "rustc".into())
+}
+
+fn probe() -> bool {
+    is_nightly() && compiles(PROBE)
+}
+
+// `rustc -vV` prints e.g. `release: 1.80.0-nightly`
+fn is_nightly() -> bool {
+    let output = match Command::new(rustc()).arg("-vV").output() {
+        Ok(output) if output.status.success() => output,
+        _ => return false,
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix("release: "))
+        .any(|release| release.contains("nightly") || release.contains("dev"))
+}
+
+fn compiles(source: &str) -> bool {
+    let out_dir = match env::var_os("OUT_DIR") {
+        Some(out_dir) => PathBuf::from(out_dir),
+        None => return false,
+    };
+    let probe_rs = out_dir.join("probe.rs");
+    if fs::write(&probe_rs, source).is_err() {
+        return false;
+    }
+    Command::new(rustc())
+        .args([
+            "--edition",
+            "2021",
+            "--crate-type",
+            "lib",
+            "--emit",
+            "metadata",
+        ])
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg(&probe_rs)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_or(false, |status| status.success())
+}
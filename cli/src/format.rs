@@ -0,0 +1,496 @@
+use crate::lines::LineOffsets;
+use proc_macro2::{
+    Delimiter, Group, Ident, LineColumn, Punct, Spacing, Span, TokenStream as TokenStream2,
+    TokenTree as TokenTree2,
+};
+use syn::visit::{self, Visit};
+use unconst_trait_impl_core::{
+    expand_item_fn, expand_item_impl, expand_item_trait, expand_items, Expansion, Mode,
+};
+
+// prettyplease can't print the nightly syntax, so it is swapped for stable markers that
+// are swapped back once printed:
+//
+// `~const Trait`         `__unconst_tilde_const::Trait`
+// `[const] Trait`        `__unconst_bracket_const::Trait`
+// `impl const Trait`     `impl __unconst_const::Trait`
+// `const impl Trait`     `impl __unconst_const_impl::Trait`
+// `const trait Trait`    `trait __unconst_const_trait__Trait`
+// `const || ..`          `#[__unconst_const_closure] || ..`
+// `cfg(predicate) =>`    `#![__unconst_cfg(predicate)]`
+const TILDE_CONST: &str = "__unconst_tilde_const";
+const BRACKET_CONST: &str = "__unconst_bracket_const";
+const CONST: &str = "__unconst_const";
+const CONST_IMPL: &str = "__unconst_const_impl";
+const CONST_TRAIT: &str = "__unconst_const_trait__";
+const CONST_CLOSURE: &str = "__unconst_const_closure";
+const CFG: &str = "__unconst_cfg";
+
+type Expand = fn(TokenStream2, Mode) -> Expansion;
+
+fn invoked(ident: &Ident) -> Option<Expand> {
+    if ident == "unconst_trait_impl" {
+        Some(expand_item_impl)
+    } else if ident == "unconst_trait" {
+        Some(expand_item_trait)
+    } else if ident == "unconst_fn" {
+        Some(expand_item_fn)
+    } else if ident == "unconst" {
+        Some(expand_items)
+    } else {
+        None
+    }
+}
+
+/// What [`format`] makes of a file
+pub struct Formatted {
+    /// The formatted file, unless it already is
+    pub content: Option<String>,
+    /// The invocations left as they are, each with the reason why
+    pub skipped: Vec<syn::Error>,
+}
+
+/// The file with the contents of the `unconst_trait_impl` invocations pretty-printed
+///
+/// Only the contents of the invocations change, the rest of the file is left to rustfmt.
+/// Invocations with comments, which the printing would drop, or whose contents can't be
+/// printed are left as they are.
+pub fn format(content: &str) -> syn::Result<Formatted> {
+    let tokens: TokenStream2 = syn::parse_str(content)?;
+    let mut invocations = Vec::new();
+    find_invocations(tokens, &mut invocations);
+
+    let lines = LineOffsets::new(content);
+    let mut formatted = content.to_string();
+    let mut changed = false;
+    let mut skipped = Vec::new();
+    // From the last one, so that the offsets of the others stay valid
+    for (expand, path_start, group) in invocations.into_iter().rev() {
+        if has_comments(&lines, &group) {
+            let message = "left as it is, as printing its contents would drop their comments";
+            skipped.push(syn::Error::new(group.span_open(), message));
+            continue;
+        }
+        let indent = lines.indent(path_start);
+        let contents = match format_contents(expand, group.stream(), indent)? {
+            Some(contents) => contents,
+            None => {
+                let message = "left as it is, as its contents can't be printed";
+                skipped.push(syn::Error::new(group.span_open(), message));
+                continue;
+            }
+        };
+        let start = lines.offset(group.span_open().end());
+        let end = lines.offset(group.span_close().start());
+        if formatted[start..end] != contents {
+            formatted.replace_range(start..end, &contents);
+            changed = true;
+        }
+    }
+    skipped.reverse();
+    Ok(Formatted {
+        content: if changed { Some(formatted) } else { None },
+        skipped,
+    })
+}
+
+// Doc comments are attributes, and as such tokens, while the other comments only show in the
+// text between the tokens
+fn has_comments(lines: &LineOffsets, group: &Group) -> bool {
+    let mut end = lines.offset(group.span_open().end());
+    has_comments_within(lines, group, &mut end)
+}
+
+// `end` is where the text checked so far ends
+fn has_comments_within(lines: &LineOffsets, group: &Group, end: &mut usize) -> bool {
+    for tt in group.stream() {
+        let commented = match &tt {
+            TokenTree2::Group(inner) => {
+                has_comments_before(lines, inner.span_open(), end)
+                    || has_comments_within(lines, inner, end)
+            }
+            tt => has_comments_before(lines, tt.span(), end),
+        };
+        if commented {
+            return true;
+        }
+    }
+    has_comments_before(lines, group.span_close(), end)
+}
+
+fn has_comments_before(lines: &LineOffsets, span: Span, end: &mut usize) -> bool {
+    let start = lines.offset(span.start());
+    // The tokens of a doc comment all span the whole of it
+    let commented = start > *end && !lines.content()[*end..start].trim().is_empty();
+    *end = (*end).max(lines.offset(span.end()));
+    commented
+}
+
+fn find_invocations(tokens: TokenStream2, invocations: &mut Vec<(Expand, LineColumn, Group)>) {
+    let tokens: Vec<TokenTree2> = tokens.into_iter().collect();
+    let mut i = 0;
+    while i < tokens.len() {
+        if let (
+            TokenTree2::Ident(ident),
+            Some(TokenTree2::Punct(bang)),
+            Some(TokenTree2::Group(group)),
+        ) = (&tokens[i], tokens.get(i + 1), tokens.get(i + 2))
+        {
+            if let (Some(expand), '!') = (invoked(ident), bang.as_char()) {
+                // The path of the invocation starts at the first of its segments
+                let mut start = i;
+                while start >= 2 && is_path_sep(&tokens[start - 2..start]) {
+                    start -= if start >= 3 && matches!(tokens[start - 3], TokenTree2::Ident(_)) {
+                        3
+                    } else {
+                        2
+                    };
+                }
+                invocations.push((expand, tokens[start].span().start(), group.clone()));
+                i += 3;
+                continue;
+            }
+        }
+        if let TokenTree2::Group(group) = &tokens[i] {
+            find_invocations(group.stream(), invocations);
+        }
+        i += 1;
+    }
+}
+
+fn is_path_sep(tokens: &[TokenTree2]) -> bool {
+    match tokens {
+        [TokenTree2::Punct(first), TokenTree2::Punct(second)] => {
+            first.as_char() == ':' && first.spacing() == Spacing::Joint && second.as_char() == ':'
+        }
+        _ => false,
+    }
+}
+
+fn format_contents(
+    expand: Expand,
+    contents: TokenStream2,
+    indent: &str,
+) -> syn::Result<Option<String>> {
+    // The contents are checked with the parser of the macros themselves
+    expand(contents.clone(), Mode::Passthrough).into_result()?;
+
+    let tokens: Vec<TokenTree2> = contents.into_iter().collect();
+    let mut file = TokenStream2::new();
+    let mut rest = &tokens[..];
+    // #![unconst(...)]
+    while let [TokenTree2::Punct(pound), TokenTree2::Punct(bang), TokenTree2::Group(_), ..] = rest {
+        if pound.as_char() != '#' || bang.as_char() != '!' {
+            break;
+        }
+        file.extend(rest[..3].iter().cloned());
+        rest = &rest[3..];
+    }
+    // cfg(predicate) =>
+    if let [TokenTree2::Ident(cfg), TokenTree2::Group(predicate), TokenTree2::Punct(eq), TokenTree2::Punct(gt), ..] =
+        rest
+    {
+        if cfg == "cfg" && eq.as_char() == '=' && gt.as_char() == '>' {
+            let args = Group::new(Delimiter::Parenthesis, predicate.stream());
+            let meta = vec![ident(CFG), TokenTree2::Group(args)];
+            file.extend(inner_attr(meta.into_iter().collect()));
+            rest = &rest[4..];
+        }
+    }
+    file.extend(mark(rest.iter().cloned().collect()));
+
+    let file: syn::File = match syn::parse2(file) {
+        Ok(file) if is_printable(&file) => file,
+        _ => return Ok(None),
+    };
+    let printed = unmark(&prettyplease::unparse(&file));
+    // A marker that went amiss would show up here
+    let reparsed = printed.parse::<TokenStream2>();
+    if !reparsed.map_or(false, |tokens| {
+        expand(tokens, Mode::Passthrough).error.is_none()
+    }) {
+        return Ok(None);
+    }
+
+    let mut contents = String::from("\n");
+    for line in printed.lines() {
+        if !line.is_empty() {
+            contents.push_str(indent);
+            contents.push_str("    ");
+            contents.push_str(line);
+        }
+        contents.push('\n');
+    }
+    contents.push_str(indent);
+    Ok(Some(contents))
+}
+
+// prettyplease panics on the syntax that syn leaves verbatim, such as inline const blocks
+fn is_printable(file: &syn::File) -> bool {
+    let mut verbatim = Verbatim(false);
+    verbatim.visit_file(file);
+    !verbatim.0
+}
+
+struct Verbatim(bool);
+
+impl<'ast> Visit<'ast> for Verbatim {
+    fn visit_expr(&mut self, expr: &'ast syn::Expr) {
+        self.0 |= matches!(expr, syn::Expr::Verbatim(tokens) if !tokens.is_empty());
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_item(&mut self, item: &'ast syn::Item) {
+        self.0 |= match item {
+            syn::Item::Verbatim(tokens) => !tokens.is_empty(),
+            syn::Item::Macro2(_) => true,
+            _ => false,
+        };
+        visit::visit_item(self, item);
+    }
+
+    fn visit_foreign_item(&mut self, item: &'ast syn::ForeignItem) {
+        self.0 |= matches!(item, syn::ForeignItem::Verbatim(tokens) if !tokens.is_empty());
+        visit::visit_foreign_item(self, item);
+    }
+
+    fn visit_impl_item(&mut self, item: &'ast syn::ImplItem) {
+        self.0 |= matches!(item, syn::ImplItem::Verbatim(tokens) if !tokens.is_empty());
+        visit::visit_impl_item(self, item);
+    }
+
+    fn visit_trait_item(&mut self, item: &'ast syn::TraitItem) {
+        self.0 |= matches!(item, syn::TraitItem::Verbatim(tokens) if !tokens.is_empty());
+        visit::visit_trait_item(self, item);
+    }
+
+    fn visit_pat(&mut self, pat: &'ast syn::Pat) {
+        self.0 |= matches!(pat, syn::Pat::Verbatim(tokens) if !tokens.is_empty());
+        visit::visit_pat(self, pat);
+    }
+
+    fn visit_type(&mut self, ty: &'ast syn::Type) {
+        self.0 |= matches!(ty, syn::Type::Verbatim(tokens) if !tokens.is_empty());
+        visit::visit_type(self, ty);
+    }
+}
+
+fn punct(ch: char, spacing: Spacing) -> TokenTree2 {
+    TokenTree2::Punct(Punct::new(ch, spacing))
+}
+
+fn ident(name: &str) -> TokenTree2 {
+    TokenTree2::Ident(Ident::new(name, Span::call_site()))
+}
+
+// `marker::`
+fn marker_path(marker: &str) -> Vec<TokenTree2> {
+    vec![
+        ident(marker),
+        punct(':', Spacing::Joint),
+        punct(':', Spacing::Alone),
+    ]
+}
+
+fn attr(style: &[TokenTree2], meta: TokenStream2) -> Vec<TokenTree2> {
+    let mut tokens = style.to_vec();
+    tokens.push(TokenTree2::Group(Group::new(Delimiter::Bracket, meta)));
+    tokens
+}
+
+fn inner_attr(meta: TokenStream2) -> Vec<TokenTree2> {
+    attr(
+        &[punct('#', Spacing::Alone), punct('!', Spacing::Alone)],
+        meta,
+    )
+}
+
+fn is_ident(tt: Option<&TokenTree2>, name: &str) -> bool {
+    matches!(tt, Some(TokenTree2::Ident(ident)) if ident == name)
+}
+
+fn is_punct(tt: Option<&TokenTree2>, ch: char) -> bool {
+    matches!(tt, Some(TokenTree2::Punct(punct)) if punct.as_char() == ch)
+}
+
+fn is_bracket_const(group: &Group) -> bool {
+    let inner: Vec<TokenTree2> = group.stream().into_iter().collect();
+    group.delimiter() == Delimiter::Bracket && inner.len() == 1 && is_ident(inner.first(), "const")
+}
+
+// `const` followed by `impl`, `trait`, or either of them after `unsafe` or `auto`
+fn const_item_keyword(tokens: &[TokenTree2]) -> Option<&'static str> {
+    for tt in tokens {
+        if is_ident(Some(tt), "impl") {
+            return Some("impl");
+        } else if is_ident(Some(tt), "trait") {
+            return Some("trait");
+        } else if !is_ident(Some(tt), "unsafe") && !is_ident(Some(tt), "auto") {
+            return None;
+        }
+    }
+    None
+}
+
+fn mark(tokens: TokenStream2) -> TokenStream2 {
+    let tokens: Vec<TokenTree2> = tokens.into_iter().collect();
+    let mut marked = Vec::new();
+    // Set by `const impl` and `const trait` until the keyword is reached
+    let mut pending: Option<&'static str> = None;
+    let mut i = 0;
+    while i < tokens.len() {
+        let tt = &tokens[i];
+        let prev = i.checked_sub(1).map(|prev| &tokens[prev]);
+        let next = tokens.get(i + 1);
+        match tt {
+            TokenTree2::Punct(punct) if punct.as_char() == '~' && is_ident(next, "const") => {
+                marked.extend(marker_path(TILDE_CONST));
+                i += 2;
+                continue;
+            }
+            TokenTree2::Group(group) if is_bracket_const(group) => {
+                marked.extend(marker_path(BRACKET_CONST));
+            }
+            TokenTree2::Ident(ident) if ident == "const" => {
+                if let Some(keyword) = const_item_keyword(&tokens[i + 1..]) {
+                    pending = Some(keyword);
+                } else if is_punct(next, '|') || is_ident(next, "move") {
+                    marked.extend(attr(
+                        &[punct('#', Spacing::Alone)],
+                        std::iter::once(self::ident(CONST_CLOSURE)).collect(),
+                    ));
+                } else if !matches!(next, Some(TokenTree2::Group(_)))
+                    && (is_ident(prev, "impl")
+                        || is_ident(prev, "dyn")
+                        || is_punct(prev, '+')
+                        || is_punct(prev, ':')
+                        || is_punct(prev, '>'))
+                {
+                    marked.extend(marker_path(CONST));
+                } else {
+                    marked.push(tt.clone());
+                }
+            }
+            TokenTree2::Ident(ident) if ident == "impl" && pending == Some("impl") => {
+                pending = None;
+                marked.push(tt.clone());
+                i += 1;
+                // The generics of the impl come before the marked path
+                if is_punct(tokens.get(i), '<') {
+                    let start = i;
+                    let mut depth = 0;
+                    while let Some(tt) = tokens.get(i) {
+                        let arrow = is_punct(tokens.get(i.wrapping_sub(1)), '-');
+                        if is_punct(Some(tt), '<') {
+                            depth += 1;
+                        } else if is_punct(Some(tt), '>') && !arrow {
+                            depth -= 1;
+                        }
+                        i += 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    // and may have nightly syntax of their own
+                    marked.extend(mark(tokens[start..i].iter().cloned().collect()));
+                }
+                if is_punct(tokens.get(i), '!') {
+                    marked.push(tokens[i].clone());
+                    i += 1;
+                }
+                marked.extend(marker_path(CONST_IMPL));
+                continue;
+            }
+            TokenTree2::Ident(ident) if ident == "trait" && pending == Some("trait") => {
+                pending = None;
+                marked.push(tt.clone());
+                if let Some(TokenTree2::Ident(name)) = next {
+                    let renamed = Ident::new(&format!("{}{}", CONST_TRAIT, name), name.span());
+                    marked.push(TokenTree2::Ident(renamed));
+                    i += 2;
+                    continue;
+                }
+            }
+            TokenTree2::Group(group) => {
+                let mut marked_group = Group::new(group.delimiter(), mark(group.stream()));
+                marked_group.set_span(group.span());
+                marked.push(TokenTree2::Group(marked_group));
+            }
+            tt => marked.push(tt.clone()),
+        }
+        i += 1;
+    }
+    marked.into_iter().collect()
+}
+
+// The start of the last occurrence of `word` in `text` as a whole word
+fn rfind_word(text: &str, word: &str) -> Option<usize> {
+    let is_word = |ch: char| ch.is_alphanumeric() || ch == '_';
+    let mut end = text.len();
+    while let Some(idx) = text[..end].rfind(word) {
+        let before = text[..idx].chars().next_back();
+        let after = text[idx + word.len()..].chars().next();
+        if !before.map_or(false, is_word) && !after.map_or(false, is_word) {
+            return Some(idx);
+        }
+        end = idx;
+    }
+    None
+}
+
+// Moves back over the qualifiers that come between `const` and the keyword
+fn qualifiers_start(text: &str, mut idx: usize, qualifiers: &[&str]) -> usize {
+    loop {
+        let before = text[..idx].trim_end();
+        match qualifiers
+            .iter()
+            .find(|qualifier| before.ends_with(*qualifier))
+        {
+            Some(qualifier) => idx = before.len() - qualifier.len(),
+            None => return idx,
+        }
+    }
+}
+
+// Removes `marker` and puts `const` before the `keyword` that precedes it
+fn unmark_keyword(text: &mut String, marker: &str, keyword: &str, qualifiers: &[&str]) {
+    while let Some(idx) = text.find(marker) {
+        text.replace_range(idx..idx + marker.len(), "");
+        if let Some(keyword) = rfind_word(&text[..idx], keyword) {
+            let start = qualifiers_start(text, keyword, qualifiers);
+            text.insert_str(start, "const ");
+        }
+    }
+}
+
+fn unmark(printed: &str) -> String {
+    let mut text = String::new();
+    for line in printed.lines() {
+        let trimmed = line.trim_start();
+        let cfg = format!("#![{}(", CFG);
+        match trimmed.strip_prefix(&cfg) {
+            Some(predicate) if predicate.ends_with(")]") => {
+                text.push_str(&line[..line.len() - trimmed.len()]);
+                text.push_str("cfg(");
+                text.push_str(&predicate[..predicate.len() - 2]);
+                text.push_str(") =>");
+            }
+            _ => text.push_str(line),
+        }
+        text.push('\n');
+    }
+    let mut text = text
+        .replace(&format!("{}::", TILDE_CONST), "~const ")
+        .replace(&format!("{}::", BRACKET_CONST), "[const] ")
+        .replace(&format!("{}::", CONST), "const ");
+    // The attribute may be followed by a line break
+    let closure = format!("#[{}]", CONST_CLOSURE);
+    while let Some(idx) = text.find(&closure) {
+        let rest = &text[idx + closure.len()..];
+        let end = text.len() - rest.trim_start().len();
+        text.replace_range(idx..end, "const ");
+    }
+    unmark_keyword(&mut text, &format!("{}::", CONST_IMPL), "impl", &["unsafe"]);
+    unmark_keyword(&mut text, CONST_TRAIT, "trait", &["unsafe", "auto"]);
+    text
+}
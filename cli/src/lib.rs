@@ -0,0 +1,9 @@
+//! The library behind `cargo unconst`, which rewrites Rust sources that use the nightly const
+//! trait syntax into stable Rust, and formats the contents of the `unconst_trait_impl` macros.
+
+mod format;
+mod lines;
+mod rewrite;
+
+pub use format::{format, Formatted};
+pub use rewrite::rewrite;
@@ -0,0 +1,32 @@
+use proc_macro2::LineColumn;
+
+// Converts the line-column locations of proc_macro2 into byte offsets
+pub(crate) struct LineOffsets<'a> {
+    content: &'a str,
+    starts: Vec<usize>,
+}
+
+impl<'a> LineOffsets<'a> {
+    pub(crate) fn new(content: &'a str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(content.match_indices('\n').map(|(idx, _)| idx + 1));
+        Self { content, starts }
+    }
+
+    pub(crate) fn content(&self) -> &'a str {
+        self.content
+    }
+
+    pub(crate) fn offset(&self, location: LineColumn) -> usize {
+        let start = self.starts[location.line - 1];
+        self.content[start..]
+            .char_indices()
+            .nth(location.column)
+            .map_or(self.content.len(), |(idx, _)| start + idx)
+    }
+
+    pub(crate) fn indent(&self, location: LineColumn) -> &'a str {
+        let line = &self.content[self.starts[location.line - 1]..];
+        &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+    }
+}
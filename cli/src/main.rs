@@ -0,0 +1,212 @@
+//! `cargo unconst` rewrites Rust sources that use the nightly const trait syntax, whether in
+//! the `unconst_trait_impl` macros or bare, into stable Rust. `cargo unconst fmt` formats the
+//! contents of the macros instead.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+const USAGE: &str = "\
+Rewrites the nightly const trait syntax of Rust sources into stable Rust
+
+Usage: cargo unconst [fmt] [OPTIONS] <PATH>...
+
+Commands:
+  fmt                  Format the contents of the macro invocations instead, keeping the
+                       nightly syntax
+
+Arguments:
+  <PATH>...            Files to rewrite, or directories to rewrite the .rs files of
+
+Options:
+      --check          List the files that would be rewritten and fail if there are any
+      --diff           Print the changes as a unified diff instead of writing them
+  -o, --out-dir <DIR>  Write the files under DIR instead of in place
+  -h, --help           Print this help
+";
+
+#[derive(Default)]
+struct Args {
+    fmt: bool,
+    check: bool,
+    diff: bool,
+    out_dir: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.peekable();
+    if args.peek().map(String::as_str) == Some("fmt") {
+        args.next();
+        parsed.fmt = true;
+    }
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => parsed.check = true,
+            "--diff" => parsed.diff = true,
+            "-o" | "--out-dir" => match args.next() {
+                Some(dir) => parsed.out_dir = Some(PathBuf::from(dir)),
+                None => return Err(format!("`{}` expects a directory", arg)),
+            },
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                process::exit(0);
+            }
+            _ if arg.starts_with('-') => return Err(format!("unexpected option `{}`", arg)),
+            _ => parsed.paths.push(PathBuf::from(arg)),
+        }
+    }
+    if parsed.paths.is_empty() {
+        return Err("expected at least one path".to_string());
+    }
+    Ok(parsed)
+}
+
+/// A file to rewrite, along with its path relative to the output directory
+struct Source {
+    path: PathBuf,
+    relative: PathBuf,
+}
+
+fn collect(path: &Path, relative: PathBuf, sources: &mut Vec<Source>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        sources.push(Source {
+            path: path.to_path_buf(),
+            relative,
+        });
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(path)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = entry.file_name();
+        let name_str = name.to_string_lossy();
+        let path = entry.path();
+        if path.is_dir() {
+            if name_str.starts_with('.') || name_str == "target" {
+                continue;
+            }
+        } else if path.extension().map_or(true, |ext| ext != "rs") {
+            continue;
+        }
+        collect(&path, relative.join(&name), sources)?;
+    }
+    Ok(())
+}
+
+// One line for each of the errors combined in `err`, after the first one prefixed with `level`
+fn located(level: &str, path: &Path, err: &syn::Error) -> String {
+    let lines: Vec<String> = err
+        .into_iter()
+        .map(|err| {
+            let start = err.span().start();
+            format!(
+                "{}:{}:{}: {}",
+                path.display(),
+                start.line,
+                start.column + 1,
+                err
+            )
+        })
+        .collect();
+    lines.join(&format!("\n{}: ", level))
+}
+
+fn diff(path: &Path, old: &str, new: &str) -> String {
+    let path = path.display().to_string();
+    similar::TextDiff::from_lines(old, new)
+        .unified_diff()
+        .header(&path, &path)
+        .to_string()
+}
+
+// Whether `source` is rewritten, i.e. differs from the result
+fn process(args: &Args, source: &Source) -> Result<bool, String> {
+    let path = &source.path;
+    let content = fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let rewritten = if args.fmt {
+        cargo_unconst::format(&content).map(|formatted| {
+            for err in &formatted.skipped {
+                eprintln!("warning: {}", located("warning", path, err));
+            }
+            formatted.content
+        })
+    } else {
+        cargo_unconst::rewrite(&content)
+    };
+    let rewritten = rewritten.map_err(|err| located("error", path, &err))?;
+    if let Some(rewritten) = &rewritten {
+        if args.check {
+            println!("{}", path.display());
+        }
+        if args.diff {
+            print!("{}", diff(path, &content, rewritten));
+        }
+    }
+    if args.check || args.diff {
+        return Ok(rewritten.is_some());
+    }
+    let target = match &args.out_dir {
+        Some(out_dir) => out_dir.join(&source.relative),
+        None => path.clone(),
+    };
+    let write = |content: &str| -> std::io::Result<()> {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, content)
+    };
+    let written = match (&rewritten, &args.out_dir) {
+        (Some(rewritten), _) => write(rewritten),
+        // The output directory gets the whole tree, unchanged files included
+        (None, Some(_)) => write(&content),
+        (None, None) => Ok(()),
+    };
+    written.map_err(|err| format!("{}: {}", target.display(), err))?;
+    Ok(rewritten.is_some())
+}
+
+fn main() {
+    let mut args = env::args().skip(1).peekable();
+    // `cargo unconst ...` runs `cargo-unconst unconst ...`
+    if args.peek().map(String::as_str) == Some("unconst") {
+        args.next();
+    }
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(err) => {
+            eprint!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let mut sources = Vec::new();
+    let mut failed = false;
+    for path in &args.paths {
+        let relative = match path.file_name() {
+            Some(name) if !path.is_dir() => PathBuf::from(name),
+            _ => PathBuf::new(),
+        };
+        if let Err(err) = collect(path, relative, &mut sources) {
+            eprintln!("error: {}: {}", path.display(), err);
+            failed = true;
+        }
+    }
+
+    let mut rewritten = false;
+    for source in &sources {
+        match process(&args, source) {
+            Ok(changed) => rewritten |= changed,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                failed = true;
+            }
+        }
+    }
+    if failed || (args.check && rewritten) {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::lines::LineOffsets;
+use proc_macro2::{LineColumn, TokenStream as TokenStream2};
+use unconst_trait_impl_core::Edit;
+
+/// The file rewritten into stable Rust, unless there is nothing to rewrite
+///
+/// Only the rewritten items are pretty-printed, in place of the original ones, while the rest
+/// of the file keeps its formatting and comments. See
+/// [`unconst_trait_impl_core::unconst_file`] for what is rewritten.
+pub fn rewrite(content: &str) -> syn::Result<Option<String>> {
+    // The parser skips the byte order mark, which would shift the columns of the first line
+    let (bom, content) = match content.strip_prefix('\u{feff}') {
+        Some(content) => ("\u{feff}", content),
+        None => ("", content),
+    };
+    let edits = unconst_trait_impl_core::unconst_file_edits(content)?;
+    if edits.is_empty() {
+        return Ok(None);
+    }
+
+    let lines = LineOffsets::new(content);
+    let mut rewritten = content.to_string();
+    // From the last one, so that the offsets of the others stay valid
+    for edit in edits.iter().rev() {
+        let (start, end) = match locate(&edit.original) {
+            Some(location) => location,
+            None => continue,
+        };
+        let (start, end, items) = if edit.items.is_empty() {
+            // A removed import takes its line along, unless it shares it with other code
+            let line_start = lines.offset(LineColumn {
+                line: start.line,
+                column: 0,
+            });
+            let (start, end) = (lines.offset(start), lines.offset(end));
+            let rest = &content[end..];
+            let line_end = rest.find('\n').map_or(content.len(), |idx| end + idx + 1);
+            if content[line_start..start].trim().is_empty()
+                && content[end..line_end].trim().is_empty()
+            {
+                (line_start, line_end, String::new())
+            } else {
+                (start, end, String::new())
+            }
+        } else {
+            let indent = lines.indent(start);
+            (lines.offset(start), lines.offset(end), print(edit, indent))
+        };
+        rewritten.replace_range(start..end, &items);
+    }
+    Ok(Some(format!("{}{}", bom, rewritten)))
+}
+
+// Where the edit starts and ends in the file
+fn locate(original: &TokenStream2) -> Option<(LineColumn, LineColumn)> {
+    let mut tokens = original.clone().into_iter();
+    let first = tokens.next()?;
+    let end = tokens.last().unwrap_or_else(|| first.clone()).span().end();
+    Some((first.span().start(), end))
+}
+
+// The items pretty-printed at the indentation of the original ones, whose first line
+// already has it
+fn print(edit: &Edit, indent: &str) -> String {
+    let file = syn::File {
+        shebang: None,
+        attrs: Vec::new(),
+        items: edit.items.clone(),
+    };
+    let printed = prettyplease::unparse(&file);
+    let mut items = String::new();
+    for (idx, line) in printed.trim_end().lines().enumerate() {
+        if idx > 0 {
+            items.push('\n');
+            if !line.is_empty() {
+                items.push_str(indent);
+            }
+        }
+        items.push_str(line);
+    }
+    items
+}
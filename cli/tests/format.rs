@@ -0,0 +1,73 @@
+use cargo_unconst::format;
+
+#[test]
+fn formats_const_bounds_in_the_generics_of_a_const_impl() {
+    let formatted =
+        format("unconst_trait_impl! { const impl<T: ~const X> Foo for W<T> {} }\n").unwrap();
+    assert!(formatted.skipped.is_empty());
+    assert_eq!(
+        formatted.content.as_deref(),
+        Some("unconst_trait_impl! {\n    const impl<T: ~const X> Foo for W<T> {}\n}\n")
+    );
+}
+
+#[test]
+fn leaves_invocations_with_comments_as_they_are() {
+    for source in [
+        "unconst_trait_impl! {\n    // comment\n    impl const Foo for W {  }\n}\n",
+        "unconst_trait_impl! {\n    impl const Foo for W { fn f() { /* comment */ } }\n}\n",
+    ] {
+        let formatted = format(source).unwrap();
+        assert_eq!(formatted.content, None);
+        assert_eq!(formatted.skipped.len(), 1);
+    }
+}
+
+#[test]
+fn formats_invocations_with_doc_comments() {
+    let formatted =
+        format("unconst_trait_impl! {\n    /// Docs\n    impl const Foo for W {  }\n}\n").unwrap();
+    assert!(formatted.skipped.is_empty());
+    assert_eq!(
+        formatted.content.as_deref(),
+        Some("unconst_trait_impl! {\n    /// Docs\n    impl const Foo for W {}\n}\n")
+    );
+}
+
+#[test]
+fn formats_once() {
+    let source = "\
+unconst_trait_impl! {
+    cfg(feature = \"nightly\") =>
+    impl<T: [const] Default>   const Default for Wrapper<T> { fn default() -> Self { Wrapper(T::default()) } }
+}
+
+unconst_trait! { pub const trait Zeroed: ~const Default {} }
+";
+    let expected = "\
+unconst_trait_impl! {
+    cfg(feature = \"nightly\") =>
+    impl<T: [const] Default> const Default for Wrapper<T> {
+        fn default() -> Self {
+            Wrapper(T::default())
+        }
+    }
+}
+
+unconst_trait! {
+    pub const trait Zeroed: ~const Default {}
+}
+";
+    let formatted = format(source).unwrap().content.unwrap();
+    assert_eq!(formatted, expected);
+    assert_eq!(format(&formatted).unwrap().content, None);
+}
+
+#[test]
+fn leaves_invocations_with_unprintable_contents_as_they_are() {
+    let source =
+        "unconst_trait_impl! {\n    impl const Foo for W { fn f() -> u8 { const { 1 } } }\n}\n";
+    let formatted = format(source).unwrap();
+    assert_eq!(formatted.content, None);
+    assert_eq!(formatted.skipped.len(), 1);
+}
@@ -0,0 +1,53 @@
+use cargo_unconst::rewrite;
+
+#[test]
+fn rewrites_the_nightly_items_in_place() {
+    let source = "\
+// before
+use unconst_trait_impl::unconst_trait_impl;
+
+pub const fn stable() -> u8 {
+    1
+}
+
+unconst_trait_impl! {
+    impl<T: ~const Default> const Default for Wrapper<T> {
+        fn default() -> Self { Wrapper(T::default()) }
+    }
+}
+
+mod inner {
+    // inside
+    impl const Trait for Type {}
+}
+// after
+";
+    let expected = "\
+// before
+
+pub const fn stable() -> u8 {
+    1
+}
+
+impl<T: Default> Default for Wrapper<T> {
+    fn default() -> Self {
+        Wrapper(T::default())
+    }
+}
+
+mod inner {
+    // inside
+    impl Trait for Type {}
+}
+// after
+";
+    let rewritten = rewrite(source).unwrap().unwrap();
+    assert_eq!(rewritten, expected);
+    assert_eq!(rewrite(&rewritten).unwrap(), None);
+}
+
+#[test]
+fn leaves_stable_files_alone() {
+    let source = "// comment\npub const fn stable() -> u8 {\n    1\n}\n";
+    assert_eq!(rewrite(source).unwrap(), None);
+}
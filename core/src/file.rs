@@ -0,0 +1,259 @@
+use crate::{
+    expand_item_fn, expand_item_impl, expand_item_trait, expand_items, inspect::HasConstSyntax,
+    unconst::Unconst, Expansion, Item, ItemConstMod, Mode, Options,
+};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{ParseStream, Parser},
+    visit_mut::VisitMut,
+    Attribute, Meta, NestedMeta, Path, Result, UseTree,
+};
+
+/// Lowers a whole source file for good, as `cargo unconst` does
+///
+/// The invocations of the macros are replaced by their expansion, without the nightly arm
+/// of a `cfg(predicate) =>` prefix, and the imports of the macros are removed. Items written
+/// in the nightly syntax outside of the macros are lowered as well, while the other items
+/// are left as they are.
+pub fn unconst_file(content: &str) -> Result<syn::File> {
+    rewrite_file(content).map(|(file, _)| file)
+}
+
+/// A run of items of a source file that [`unconst_file_edits`] rewrites
+pub struct Edit {
+    /// The items as written, whose spans locate them in the file
+    pub original: TokenStream2,
+    /// What they are rewritten into, nothing for the removed imports
+    pub items: Vec<syn::Item>,
+}
+
+/// The rewrites of [`unconst_file`], item by item, in the order of the file
+///
+/// The edits don't overlap, so that they can be spliced into the source text while the rest
+/// of it, comments included, stays as it is. Locating them takes the `span-locations` feature
+/// of proc-macro2.
+pub fn unconst_file_edits(content: &str) -> Result<Vec<Edit>> {
+    rewrite_file(content).map(|(_, edits)| edits)
+}
+
+fn rewrite_file(content: &str) -> Result<(syn::File, Vec<Edit>)> {
+    let (shebang, content) = split_shebang(content);
+    let (attrs, items) = parse_items.parse_str(content)?;
+    let mut invocations = Invocations::default();
+    let items = items
+        .into_iter()
+        .flat_map(|item| invocations.rewrite(item))
+        .collect();
+    let file = syn::File {
+        shebang,
+        attrs,
+        items,
+    };
+    match invocations.error {
+        Some(error) => Err(error),
+        None => Ok((file, invocations.edits)),
+    }
+}
+
+// file.rs (syn 1.0.86)
+fn split_shebang(content: &str) -> (Option<String>, &str) {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    if content.starts_with("#!") && !content[2..].trim_start().starts_with('[') {
+        match content.find('\n') {
+            Some(idx) => (Some(content[..idx].to_string()), &content[idx..]),
+            None => (Some(content.to_string()), ""),
+        }
+    } else {
+        (None, content)
+    }
+}
+
+fn parse_items(input: ParseStream) -> Result<(Vec<Attribute>, Vec<Item>)> {
+    let attrs = input.call(Attribute::parse_inner)?;
+    let mut items = Vec::new();
+    while !input.is_empty() {
+        items.push(input.parse()?);
+    }
+    Ok((attrs, items))
+}
+
+type Expand = fn(TokenStream2, Mode) -> Expansion;
+
+fn invoked(path: &Path) -> Option<Expand> {
+    let ident = &path.segments.last()?.ident;
+    if ident == "unconst_trait_impl" {
+        Some(expand_item_impl)
+    } else if ident == "unconst_trait" {
+        Some(expand_item_trait)
+    } else if ident == "unconst_fn" {
+        Some(expand_item_fn)
+    } else if ident == "unconst" {
+        Some(expand_items)
+    } else {
+        None
+    }
+}
+
+// `use unconst_trait_impl::unconst_trait_impl;` and `use remove_macro_call::remove_macro_call;`
+fn imports_macros(tree: &UseTree) -> bool {
+    let ident = match tree {
+        UseTree::Path(path) => &path.ident,
+        UseTree::Name(name) => &name.ident,
+        UseTree::Rename(rename) => &rename.ident,
+        UseTree::Glob(_) | UseTree::Group(_) => return false,
+    };
+    ident == "unconst_trait_impl" || ident == "remove_macro_call"
+}
+
+// `#[cfg_attr(predicate, remove_macro_call)]` has nothing left to remove
+fn removes_macro_call(attr: &Attribute) -> bool {
+    match attr.parse_meta() {
+        Ok(Meta::List(list)) if list.path.is_ident("cfg_attr") => {
+            list.nested.iter().skip(1).all(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => path
+                    .segments
+                    .last()
+                    .map_or(false, |segment| segment.ident == "remove_macro_call"),
+                _ => false,
+            })
+        }
+        _ => false,
+    }
+}
+
+#[derive(Default)]
+struct Invocations {
+    error: Option<syn::Error>,
+    edits: Vec<Edit>,
+}
+
+impl Invocations {
+    fn record(&mut self, error: syn::Error) {
+        match &mut self.error {
+            Some(errors) => errors.combine(error),
+            None => self.error = Some(error),
+        }
+    }
+
+    // The replacement is rewritten as a whole, so that the edits within it are left out
+    fn replace(&mut self, original: TokenStream2, mut items: Vec<syn::Item>) -> Vec<syn::Item> {
+        let edits = std::mem::take(&mut self.edits);
+        for item in &mut items {
+            self.visit_item_mut(item);
+        }
+        self.edits = edits;
+        self.edits.push(Edit {
+            original,
+            items: items.clone(),
+        });
+        items
+    }
+
+    // Modules are rewritten item by item, and only the items in the nightly syntax are lowered
+    fn rewrite(&mut self, item: Item) -> Vec<syn::Item> {
+        let item = match item {
+            Item::Other(item) => return self.expand(item),
+            Item::Mod(item) => {
+                let ItemConstMod {
+                    attrs,
+                    vis,
+                    mod_token,
+                    ident,
+                    brace_token,
+                    items,
+                } = item;
+                let items = items.into_iter().flat_map(|item| self.rewrite(item));
+                return vec![syn::Item::Mod(syn::ItemMod {
+                    attrs,
+                    vis,
+                    mod_token,
+                    ident,
+                    content: Some((brace_token, items.collect())),
+                    semi: None,
+                })];
+            }
+            item => item,
+        };
+        let options = Options::default();
+        let original = item.to_token_stream();
+        match syn::parse2(original.clone()) {
+            Ok(parsed) if !item.has_const_syntax(&options) => self.expand(parsed),
+            // Reparsed, so that what the lowering leaves verbatim is visited as well
+            _ => match syn::parse2(item.unconst(&options).into_token_stream()) {
+                Ok(item) => self.replace(original, vec![item]),
+                Err(error) => {
+                    self.record(error);
+                    Vec::new()
+                }
+            },
+        }
+    }
+
+    fn expand(&mut self, item: syn::Item) -> Vec<syn::Item> {
+        let (original, attrs, expand, tokens) = match item {
+            syn::Item::Use(item) if imports_macros(&item.tree) => {
+                return self.replace(item.into_token_stream(), Vec::new());
+            }
+            syn::Item::Macro(item) => match invoked(&item.mac.path) {
+                Some(expand) => (item.to_token_stream(), item.attrs, expand, item.mac.tokens),
+                None => return vec![syn::Item::Macro(item)],
+            },
+            mut item => {
+                self.visit_item_mut(&mut item);
+                return vec![item];
+            }
+        };
+        let attrs = attrs.into_iter().filter(|attr| !removes_macro_call(attr));
+        let attrs: Vec<Attribute> = attrs.collect();
+        let expanded = expand(tokens, Mode::Rewrite)
+            .into_result()
+            .and_then(|tokens| {
+                let file: syn::File = syn::parse2(tokens)?;
+                // The attributes of the invocation, such as `cfg`, apply to each of the items
+                file.items
+                    .into_iter()
+                    .map(|item| syn::parse2(quote!(#(#attrs)* #item)))
+                    .collect()
+            });
+        match expanded {
+            Ok(items) => self.replace(original, items),
+            Err(error) => {
+                self.record(error);
+                Vec::new()
+            }
+        }
+    }
+
+    fn expand_all(&mut self, items: &mut Vec<syn::Item>) {
+        let taken = std::mem::take(items);
+        *items = taken
+            .into_iter()
+            .flat_map(|item| self.expand(item))
+            .collect();
+    }
+}
+
+// Only the items are expanded here, as they are visited by `expand` itself
+impl VisitMut for Invocations {
+    fn visit_item_mod_mut(&mut self, item: &mut syn::ItemMod) {
+        if let Some((_, items)) = &mut item.content {
+            self.expand_all(items);
+        }
+    }
+
+    fn visit_block_mut(&mut self, block: &mut syn::Block) {
+        let stmts = std::mem::take(&mut block.stmts);
+        for stmt in stmts {
+            match stmt {
+                syn::Stmt::Item(item) => block
+                    .stmts
+                    .extend(self.expand(item).into_iter().map(syn::Stmt::Item)),
+                mut stmt => {
+                    self.visit_stmt_mut(&mut stmt);
+                    block.stmts.push(stmt);
+                }
+            }
+        }
+    }
+}
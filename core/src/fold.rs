@@ -0,0 +1,568 @@
+//! Syntax tree traversal to transform the nodes of an owned const-aware syntax tree
+//!
+//! Each method of the [`Fold`] trait is a hook that can be overridden to customize the
+//! behavior when transforming the corresponding type of node. By default, every method
+//! recursively visits the substructure of the input by invoking the right visitor method of each
+//! of its fields.
+//!
+//! The nodes borrowed from syn, such as [`Type`] or [`Attribute`], are leaves: their methods do
+//! nothing by default. Use `syn::fold` to descend into them.
+//!
+//! The traversal follows `syn::fold` (syn 1.0.86) over the types of this crate.
+
+use crate::{
+    Block, BracketConst, GenericParam, Generics, ImplItem, ImplItemMethod, ImplItemType, Item,
+    ItemConstFn, ItemConstImpl, ItemConstMod, ItemConstTrait, PredicateType, Signature, Stmt,
+    TildeConst, TraitBound, TraitBoundModifier, TraitItem, TraitItemMethod, TraitItemType,
+    TypeParam, TypeParamBound, WhereClause, WherePredicate,
+};
+use syn::punctuated::{Pair, Punctuated};
+use syn::{
+    Abi, Attribute, BoundLifetimes, ConstParam, FnArg, Ident, ImplItemConst, ImplItemMacro,
+    Lifetime, LifetimeDef, Path, PredicateEq, PredicateLifetime, ReturnType, TraitItemConst,
+    TraitItemMacro, Type, Variadic, Visibility,
+};
+
+/// Syntax tree traversal to transform the nodes of an owned const-aware syntax tree
+///
+/// See the [module documentation] for details.
+///
+/// [module documentation]: self
+pub trait Fold {
+    fn fold_abi(&mut self, i: Abi) -> Abi {
+        i
+    }
+    fn fold_attribute(&mut self, i: Attribute) -> Attribute {
+        i
+    }
+    fn fold_block(&mut self, i: Block) -> Block {
+        fold_block(self, i)
+    }
+    fn fold_bound_lifetimes(&mut self, i: BoundLifetimes) -> BoundLifetimes {
+        i
+    }
+    fn fold_bracket_const(&mut self, i: BracketConst) -> BracketConst {
+        fold_bracket_const(self, i)
+    }
+    fn fold_const_param(&mut self, i: ConstParam) -> ConstParam {
+        i
+    }
+    fn fold_fn_arg(&mut self, i: FnArg) -> FnArg {
+        i
+    }
+    fn fold_generic_param(&mut self, i: GenericParam) -> GenericParam {
+        fold_generic_param(self, i)
+    }
+    fn fold_generics(&mut self, i: Generics) -> Generics {
+        fold_generics(self, i)
+    }
+    fn fold_ident(&mut self, i: Ident) -> Ident {
+        i
+    }
+    fn fold_impl_item(&mut self, i: ImplItem) -> ImplItem {
+        fold_impl_item(self, i)
+    }
+    fn fold_impl_item_const(&mut self, i: ImplItemConst) -> ImplItemConst {
+        i
+    }
+    fn fold_impl_item_macro(&mut self, i: ImplItemMacro) -> ImplItemMacro {
+        i
+    }
+    fn fold_impl_item_method(&mut self, i: ImplItemMethod) -> ImplItemMethod {
+        fold_impl_item_method(self, i)
+    }
+    fn fold_impl_item_type(&mut self, i: ImplItemType) -> ImplItemType {
+        fold_impl_item_type(self, i)
+    }
+    fn fold_item(&mut self, i: Item) -> Item {
+        fold_item(self, i)
+    }
+    fn fold_item_const_fn(&mut self, i: ItemConstFn) -> ItemConstFn {
+        fold_item_const_fn(self, i)
+    }
+    fn fold_item_const_impl(&mut self, i: ItemConstImpl) -> ItemConstImpl {
+        fold_item_const_impl(self, i)
+    }
+    fn fold_item_const_mod(&mut self, i: ItemConstMod) -> ItemConstMod {
+        fold_item_const_mod(self, i)
+    }
+    fn fold_item_const_trait(&mut self, i: ItemConstTrait) -> ItemConstTrait {
+        fold_item_const_trait(self, i)
+    }
+    fn fold_lifetime(&mut self, i: Lifetime) -> Lifetime {
+        i
+    }
+    fn fold_lifetime_def(&mut self, i: LifetimeDef) -> LifetimeDef {
+        i
+    }
+    fn fold_path(&mut self, i: Path) -> Path {
+        i
+    }
+    fn fold_predicate_eq(&mut self, i: PredicateEq) -> PredicateEq {
+        i
+    }
+    fn fold_predicate_lifetime(&mut self, i: PredicateLifetime) -> PredicateLifetime {
+        i
+    }
+    fn fold_predicate_type(&mut self, i: PredicateType) -> PredicateType {
+        fold_predicate_type(self, i)
+    }
+    fn fold_return_type(&mut self, i: ReturnType) -> ReturnType {
+        i
+    }
+    fn fold_signature(&mut self, i: Signature) -> Signature {
+        fold_signature(self, i)
+    }
+    fn fold_stmt(&mut self, i: Stmt) -> Stmt {
+        fold_stmt(self, i)
+    }
+    fn fold_syn_item(&mut self, i: syn::Item) -> syn::Item {
+        i
+    }
+    fn fold_syn_stmt(&mut self, i: syn::Stmt) -> syn::Stmt {
+        i
+    }
+    fn fold_tilde_const(&mut self, i: TildeConst) -> TildeConst {
+        fold_tilde_const(self, i)
+    }
+    fn fold_trait_bound(&mut self, i: TraitBound) -> TraitBound {
+        fold_trait_bound(self, i)
+    }
+    fn fold_trait_bound_modifier(&mut self, i: TraitBoundModifier) -> TraitBoundModifier {
+        fold_trait_bound_modifier(self, i)
+    }
+    fn fold_trait_item(&mut self, i: TraitItem) -> TraitItem {
+        fold_trait_item(self, i)
+    }
+    fn fold_trait_item_const(&mut self, i: TraitItemConst) -> TraitItemConst {
+        i
+    }
+    fn fold_trait_item_macro(&mut self, i: TraitItemMacro) -> TraitItemMacro {
+        i
+    }
+    fn fold_trait_item_method(&mut self, i: TraitItemMethod) -> TraitItemMethod {
+        fold_trait_item_method(self, i)
+    }
+    fn fold_trait_item_type(&mut self, i: TraitItemType) -> TraitItemType {
+        fold_trait_item_type(self, i)
+    }
+    fn fold_type(&mut self, i: Type) -> Type {
+        i
+    }
+    fn fold_type_param(&mut self, i: TypeParam) -> TypeParam {
+        fold_type_param(self, i)
+    }
+    fn fold_type_param_bound(&mut self, i: TypeParamBound) -> TypeParamBound {
+        fold_type_param_bound(self, i)
+    }
+    fn fold_variadic(&mut self, i: Variadic) -> Variadic {
+        i
+    }
+    fn fold_visibility(&mut self, i: Visibility) -> Visibility {
+        i
+    }
+    fn fold_where_clause(&mut self, i: WhereClause) -> WhereClause {
+        fold_where_clause(self, i)
+    }
+    fn fold_where_predicate(&mut self, i: WherePredicate) -> WherePredicate {
+        fold_where_predicate(self, i)
+    }
+}
+
+pub fn fold_block<F>(f: &mut F, node: Block) -> Block
+where
+    F: Fold + ?Sized,
+{
+    Block {
+        brace_token: node.brace_token,
+        stmts: node.stmts.into_iter().map(|it| f.fold_stmt(it)).collect(),
+    }
+}
+
+pub fn fold_bracket_const<F>(f: &mut F, node: BracketConst) -> BracketConst
+where
+    F: Fold + ?Sized,
+{
+    let _ = f;
+    BracketConst {
+        bracket_token: node.bracket_token,
+        const_: node.const_,
+    }
+}
+
+pub fn fold_generic_param<F>(f: &mut F, node: GenericParam) -> GenericParam
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        GenericParam::Type(it) => GenericParam::Type(f.fold_type_param(it)),
+        GenericParam::Lifetime(it) => GenericParam::Lifetime(f.fold_lifetime_def(it)),
+        GenericParam::Const(it) => GenericParam::Const(f.fold_const_param(it)),
+    }
+}
+
+pub fn fold_generics<F>(f: &mut F, node: Generics) -> Generics
+where
+    F: Fold + ?Sized,
+{
+    Generics {
+        lt_token: node.lt_token,
+        params: lift(node.params, |it| f.fold_generic_param(it)),
+        gt_token: node.gt_token,
+        where_clause: node.where_clause.map(|it| f.fold_where_clause(it)),
+    }
+}
+
+pub fn fold_impl_item<F>(f: &mut F, node: ImplItem) -> ImplItem
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        ImplItem::Const(it) => ImplItem::Const(f.fold_impl_item_const(it)),
+        ImplItem::Method(it) => ImplItem::Method(f.fold_impl_item_method(it)),
+        ImplItem::Type(it) => ImplItem::Type(f.fold_impl_item_type(it)),
+        ImplItem::Macro(it) => ImplItem::Macro(f.fold_impl_item_macro(it)),
+        ImplItem::Verbatim(it) => ImplItem::Verbatim(it),
+    }
+}
+
+pub fn fold_impl_item_method<F>(f: &mut F, node: ImplItemMethod) -> ImplItemMethod
+where
+    F: Fold + ?Sized,
+{
+    ImplItemMethod {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_attribute(it))
+            .collect(),
+        vis: f.fold_visibility(node.vis),
+        defaultness: node.defaultness,
+        sig: f.fold_signature(node.sig),
+        block: f.fold_block(node.block),
+    }
+}
+
+pub fn fold_impl_item_type<F>(f: &mut F, node: ImplItemType) -> ImplItemType
+where
+    F: Fold + ?Sized,
+{
+    ImplItemType {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_attribute(it))
+            .collect(),
+        vis: f.fold_visibility(node.vis),
+        defaultness: node.defaultness,
+        type_token: node.type_token,
+        ident: f.fold_ident(node.ident),
+        generics: f.fold_generics(node.generics),
+        colon_token: node.colon_token,
+        bounds: lift(node.bounds, |it| f.fold_type_param_bound(it)),
+        ty: node.ty.map(|(eq_token, ty)| (eq_token, f.fold_type(ty))),
+        trailing_where_clause: node.trailing_where_clause.map(|it| f.fold_where_clause(it)),
+        semi_token: node.semi_token,
+    }
+}
+
+pub fn fold_item<F>(f: &mut F, node: Item) -> Item
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        Item::Impl(it) => Item::Impl(f.fold_item_const_impl(it)),
+        Item::Trait(it) => Item::Trait(f.fold_item_const_trait(it)),
+        Item::Fn(it) => Item::Fn(f.fold_item_const_fn(it)),
+        Item::Mod(it) => Item::Mod(f.fold_item_const_mod(it)),
+        Item::Other(it) => Item::Other(f.fold_syn_item(it)),
+    }
+}
+
+pub fn fold_item_const_fn<F>(f: &mut F, node: ItemConstFn) -> ItemConstFn
+where
+    F: Fold + ?Sized,
+{
+    ItemConstFn {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_attribute(it))
+            .collect(),
+        vis: f.fold_visibility(node.vis),
+        sig: f.fold_signature(node.sig),
+        block: Box::new(f.fold_block(*node.block)),
+    }
+}
+
+pub fn fold_item_const_impl<F>(f: &mut F, node: ItemConstImpl) -> ItemConstImpl
+where
+    F: Fold + ?Sized,
+{
+    ItemConstImpl {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_attribute(it))
+            .collect(),
+        defaultness: node.defaultness,
+        leading_constness: node.leading_constness,
+        unsafety: node.unsafety,
+        impl_token: node.impl_token,
+        generics: f.fold_generics(node.generics),
+        constness: node.constness,
+        trait_: node
+            .trait_
+            .map(|(bang, path, for_token)| (bang, f.fold_path(path), for_token)),
+        self_ty: Box::new(f.fold_type(*node.self_ty)),
+        brace_token: node.brace_token,
+        items: node
+            .items
+            .into_iter()
+            .map(|it| f.fold_impl_item(it))
+            .collect(),
+    }
+}
+
+pub fn fold_item_const_mod<F>(f: &mut F, node: ItemConstMod) -> ItemConstMod
+where
+    F: Fold + ?Sized,
+{
+    ItemConstMod {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_attribute(it))
+            .collect(),
+        vis: f.fold_visibility(node.vis),
+        mod_token: node.mod_token,
+        ident: f.fold_ident(node.ident),
+        brace_token: node.brace_token,
+        items: node.items.into_iter().map(|it| f.fold_item(it)).collect(),
+    }
+}
+
+pub fn fold_item_const_trait<F>(f: &mut F, node: ItemConstTrait) -> ItemConstTrait
+where
+    F: Fold + ?Sized,
+{
+    ItemConstTrait {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_attribute(it))
+            .collect(),
+        vis: f.fold_visibility(node.vis),
+        constness: node.constness,
+        unsafety: node.unsafety,
+        auto_token: node.auto_token,
+        trait_token: node.trait_token,
+        ident: f.fold_ident(node.ident),
+        generics: f.fold_generics(node.generics),
+        colon_token: node.colon_token,
+        supertraits: lift(node.supertraits, |it| f.fold_type_param_bound(it)),
+        brace_token: node.brace_token,
+        items: node
+            .items
+            .into_iter()
+            .map(|it| f.fold_trait_item(it))
+            .collect(),
+    }
+}
+
+pub fn fold_predicate_type<F>(f: &mut F, node: PredicateType) -> PredicateType
+where
+    F: Fold + ?Sized,
+{
+    PredicateType {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_attribute(it))
+            .collect(),
+        lifetimes: node.lifetimes.map(|it| f.fold_bound_lifetimes(it)),
+        bounded_ty: f.fold_type(node.bounded_ty),
+        colon_token: node.colon_token,
+        bounds: lift(node.bounds, |it| f.fold_type_param_bound(it)),
+    }
+}
+
+pub fn fold_signature<F>(f: &mut F, node: Signature) -> Signature
+where
+    F: Fold + ?Sized,
+{
+    Signature {
+        constness: node.constness,
+        asyncness: node.asyncness,
+        unsafety: node.unsafety,
+        abi: node.abi.map(|it| f.fold_abi(it)),
+        fn_token: node.fn_token,
+        ident: f.fold_ident(node.ident),
+        generics: f.fold_generics(node.generics),
+        paren_token: node.paren_token,
+        inputs: lift(node.inputs, |it| f.fold_fn_arg(it)),
+        variadic: node.variadic.map(|it| f.fold_variadic(it)),
+        output: f.fold_return_type(node.output),
+    }
+}
+
+pub fn fold_stmt<F>(f: &mut F, node: Stmt) -> Stmt
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        Stmt::Item(it) => Stmt::Item(f.fold_item(it)),
+        Stmt::Other(it) => Stmt::Other(f.fold_syn_stmt(it)),
+    }
+}
+
+pub fn fold_tilde_const<F>(f: &mut F, node: TildeConst) -> TildeConst
+where
+    F: Fold + ?Sized,
+{
+    let _ = f;
+    TildeConst {
+        tilde: node.tilde,
+        const_: node.const_,
+    }
+}
+
+pub fn fold_trait_bound<F>(f: &mut F, node: TraitBound) -> TraitBound
+where
+    F: Fold + ?Sized,
+{
+    TraitBound {
+        paren_token: node.paren_token,
+        modifier: f.fold_trait_bound_modifier(node.modifier),
+        lifetimes: node.lifetimes.map(|it| f.fold_bound_lifetimes(it)),
+        path: f.fold_path(node.path),
+    }
+}
+
+pub fn fold_trait_bound_modifier<F>(f: &mut F, node: TraitBoundModifier) -> TraitBoundModifier
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        TraitBoundModifier::None => TraitBoundModifier::None,
+        TraitBoundModifier::Maybe(it) => TraitBoundModifier::Maybe(it),
+        TraitBoundModifier::TildeConst(it) => {
+            TraitBoundModifier::TildeConst(f.fold_tilde_const(it))
+        }
+        TraitBoundModifier::BracketConst(it) => {
+            TraitBoundModifier::BracketConst(f.fold_bracket_const(it))
+        }
+        TraitBoundModifier::Const(it) => TraitBoundModifier::Const(it),
+    }
+}
+
+pub fn fold_trait_item<F>(f: &mut F, node: TraitItem) -> TraitItem
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        TraitItem::Const(it) => TraitItem::Const(f.fold_trait_item_const(it)),
+        TraitItem::Method(it) => TraitItem::Method(f.fold_trait_item_method(it)),
+        TraitItem::Type(it) => TraitItem::Type(f.fold_trait_item_type(it)),
+        TraitItem::Macro(it) => TraitItem::Macro(f.fold_trait_item_macro(it)),
+        TraitItem::Verbatim(it) => TraitItem::Verbatim(it),
+    }
+}
+
+pub fn fold_trait_item_method<F>(f: &mut F, node: TraitItemMethod) -> TraitItemMethod
+where
+    F: Fold + ?Sized,
+{
+    TraitItemMethod {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_attribute(it))
+            .collect(),
+        sig: f.fold_signature(node.sig),
+        default: node.default.map(|it| f.fold_block(it)),
+        semi_token: node.semi_token,
+    }
+}
+
+pub fn fold_trait_item_type<F>(f: &mut F, node: TraitItemType) -> TraitItemType
+where
+    F: Fold + ?Sized,
+{
+    TraitItemType {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_attribute(it))
+            .collect(),
+        type_token: node.type_token,
+        ident: f.fold_ident(node.ident),
+        generics: f.fold_generics(node.generics),
+        colon_token: node.colon_token,
+        bounds: lift(node.bounds, |it| f.fold_type_param_bound(it)),
+        default: node
+            .default
+            .map(|(eq_token, ty)| (eq_token, f.fold_type(ty))),
+        trailing_where_clause: node.trailing_where_clause.map(|it| f.fold_where_clause(it)),
+        semi_token: node.semi_token,
+    }
+}
+
+pub fn fold_type_param<F>(f: &mut F, node: TypeParam) -> TypeParam
+where
+    F: Fold + ?Sized,
+{
+    TypeParam {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_attribute(it))
+            .collect(),
+        ident: f.fold_ident(node.ident),
+        colon_token: node.colon_token,
+        bounds: lift(node.bounds, |it| f.fold_type_param_bound(it)),
+        eq_token: node.eq_token,
+        default: node.default.map(|it| f.fold_type(it)),
+    }
+}
+
+pub fn fold_type_param_bound<F>(f: &mut F, node: TypeParamBound) -> TypeParamBound
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        TypeParamBound::Trait(it) => TypeParamBound::Trait(f.fold_trait_bound(it)),
+        TypeParamBound::Lifetime(it) => TypeParamBound::Lifetime(f.fold_lifetime(it)),
+    }
+}
+
+pub fn fold_where_clause<F>(f: &mut F, node: WhereClause) -> WhereClause
+where
+    F: Fold + ?Sized,
+{
+    WhereClause {
+        where_token: node.where_token,
+        predicates: lift(node.predicates, |it| f.fold_where_predicate(it)),
+    }
+}
+
+pub fn fold_where_predicate<F>(f: &mut F, node: WherePredicate) -> WherePredicate
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        WherePredicate::Type(it) => WherePredicate::Type(f.fold_predicate_type(it)),
+        WherePredicate::Lifetime(it) => WherePredicate::Lifetime(f.fold_predicate_lifetime(it)),
+        WherePredicate::Eq(it) => WherePredicate::Eq(f.fold_predicate_eq(it)),
+    }
+}
+
+fn lift<T, P>(punctuated: Punctuated<T, P>, mut f: impl FnMut(T) -> T) -> Punctuated<T, P> {
+    punctuated
+        .into_pairs()
+        .map(|pair| {
+            let (value, punct) = pair.into_tuple();
+            Pair::new(f(value), punct)
+        })
+        .collect()
+}
@@ -0,0 +1,95 @@
+use crate::{TraitBound, TraitBoundModifier, TypeParamBound};
+
+impl From<TraitBoundModifier> for syn::TraitBoundModifier {
+    fn from(m: TraitBoundModifier) -> Self {
+        match m {
+            TraitBoundModifier::None
+            | TraitBoundModifier::TildeConst(_)
+            | TraitBoundModifier::BracketConst(_)
+            | TraitBoundModifier::Const(_) => syn::TraitBoundModifier::None,
+            TraitBoundModifier::Maybe(question) => syn::TraitBoundModifier::Maybe(question),
+        }
+    }
+}
+
+impl From<TraitBound> for syn::TraitBound {
+    fn from(b: TraitBound) -> Self {
+        let TraitBound {
+            paren_token,
+            modifier,
+            lifetimes,
+            path,
+        } = b;
+        Self {
+            paren_token,
+            modifier: modifier.into(),
+            lifetimes,
+            path,
+        }
+    }
+}
+
+impl From<TypeParamBound> for syn::TypeParamBound {
+    fn from(b: TypeParamBound) -> Self {
+        match b {
+            TypeParamBound::Lifetime(l) => Self::Lifetime(l),
+            TypeParamBound::Trait(t) => Self::Trait(t.into()),
+        }
+    }
+}
+
+// Currently unused
+//impl From<ItemConstImpl> for TokenStream {
+//    #[allow(unused_variables, clippy::let_and_return)]
+//    fn from(item_impl: ItemConstImpl) -> TokenStream {
+//        let ItemConstImpl {
+//            attrs,
+//            defaultness,
+//            unsafety,
+//            impl_token,
+//            generics,
+//            constness,
+//            trait_,
+//            self_ty,
+//            brace_token,
+//            items,
+//        } = item_impl;
+//        let Generics {
+//            lt_token,
+//            gt_token,
+//            params,
+//            where_clause,
+//        } = generics;
+//        let mut ts = TokenStream::new();
+//        for attr in attrs.into_iter() {
+//            ts.extend::<TokenStream>(attr.to_token_stream().into());
+//        }
+//        ts.extend::<TokenStream>(defaultness.to_token_stream().into());
+//        ts.extend::<TokenStream>(unsafety.to_token_stream().into());
+//        ts.extend::<TokenStream>(impl_token.to_token_stream().into());
+//        ts.extend::<TokenStream>(lt_token.to_token_stream().into());
+//        ts.extend::<TokenStream>(params.to_token_stream().into());
+//        ts.extend::<TokenStream>(gt_token.to_token_stream().into());
+//        ts.extend::<TokenStream>(constness.to_token_stream().into());
+//        match trait_ {
+//            None => {}
+//            Some((bang, path, for_)) => {
+//                ts.extend::<TokenStream>(bang.to_token_stream().into());
+//                ts.extend::<TokenStream>(path.to_token_stream().into());
+//                ts.extend::<TokenStream>(for_.to_token_stream().into());
+//            }
+//        };
+//        ts.extend::<TokenStream>(self_ty.to_token_stream().into());
+//        ts.extend::<TokenStream>(where_clause.to_token_stream().into());
+//        let mut nested_ts = TokenStream2::new();
+//        for item in items.into_iter() {
+//            nested_ts.extend(item.to_token_stream());
+//        }
+//        ts.extend::<TokenStream>(
+//            Group2::new(Delimiter2::Brace, nested_ts)
+//                .to_token_stream()
+//                .into(),
+//        );
+//        ts
+//    }
+//}
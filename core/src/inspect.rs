@@ -0,0 +1,317 @@
+use crate::{
+    strip, unconst::keeps_const, Block, GenericParam, Generics, ImplItem, Item, ItemConstFn,
+    ItemConstImpl, ItemConstMod, ItemConstTrait, Options, Signature, Stmt, TraitBoundModifier,
+    TraitItem, TypeParamBound, Version, WhereClause, WherePredicate,
+};
+use proc_macro2::TokenTree as TokenTree2;
+use quote::ToTokens;
+use syn::{
+    visit::{self, Visit},
+    Attribute, Expr, ExprClosure, FnArg, ReturnType,
+};
+
+impl TraitBoundModifier {
+    /// Whether the bound is `~const`, `[const]` or `const`
+    pub(crate) fn is_const(&self) -> bool {
+        match self {
+            TraitBoundModifier::TildeConst(_)
+            | TraitBoundModifier::BracketConst(_)
+            | TraitBoundModifier::Const(_) => true,
+            TraitBoundModifier::None | TraitBoundModifier::Maybe(_) => false,
+        }
+    }
+}
+
+impl TypeParamBound {
+    // Before Rust 1.61, `?Sized` was the only trait bound allowed on const fns
+    pub(crate) fn is_const_fn_trait_bound(&self) -> bool {
+        match self {
+            TypeParamBound::Trait(tb) => !matches!(tb.modifier, TraitBoundModifier::Maybe(_)),
+            TypeParamBound::Lifetime(_) => false,
+        }
+    }
+
+    pub(crate) fn is_const(&self) -> bool {
+        match self {
+            TypeParamBound::Trait(tb) => tb.modifier.is_const(),
+            TypeParamBound::Lifetime(_) => false,
+        }
+    }
+}
+
+impl WhereClause {
+    fn any_bound(&self, f: fn(&TypeParamBound) -> bool) -> bool {
+        self.predicates.iter().any(|predicate| match predicate {
+            WherePredicate::Type(ty) => ty.bounds.iter().any(f),
+            WherePredicate::Lifetime(_) | WherePredicate::Eq(_) => false,
+        })
+    }
+
+    pub(crate) fn has_const_bounds(&self) -> bool {
+        self.any_bound(TypeParamBound::is_const)
+    }
+}
+
+impl Generics {
+    fn any_bound(&self, f: fn(&TypeParamBound) -> bool) -> bool {
+        let in_params = self.params.iter().any(|param| match param {
+            GenericParam::Type(tp) => tp.bounds.iter().any(f),
+            GenericParam::Lifetime(_) | GenericParam::Const(_) => false,
+        });
+        in_params
+            || self
+                .where_clause
+                .as_ref()
+                .map_or(false, |where_clause| where_clause.any_bound(f))
+    }
+
+    /// Whether a const fn under these generics relies on `const_fn_trait_bound`
+    pub(crate) fn has_trait_bounds(&self) -> bool {
+        self.any_bound(TypeParamBound::is_const_fn_trait_bound)
+    }
+
+    /// Whether a const fn under these generics relies on `const_trait_impl`
+    pub(crate) fn has_const_bounds(&self) -> bool {
+        self.any_bound(TypeParamBound::is_const)
+    }
+}
+
+impl Signature {
+    /// Whether the argument or return types contain `impl ~const Trait`
+    pub(crate) fn has_const_types(&self) -> bool {
+        let in_inputs = self.inputs.iter().any(|input| match input {
+            FnArg::Typed(arg) => strip::has_const_modifiers(&arg.ty.to_token_stream()),
+            FnArg::Receiver(_) => false,
+        });
+        in_inputs
+            || match &self.output {
+                ReturnType::Type(_, ty) => strip::has_const_modifiers(&ty.to_token_stream()),
+                ReturnType::Default => false,
+            }
+    }
+}
+
+impl Block {
+    /// Whether a closure among the statements was written as a const closure
+    pub fn is_const_closure(closure: &ExprClosure) -> bool {
+        closure.attrs.iter().any(strip::is_const_closure_marker)
+    }
+}
+
+impl ImplItem {
+    /// The outer attributes of the item, none for verbatim ones
+    pub(crate) fn attrs(&self) -> &[Attribute] {
+        match self {
+            ImplItem::Const(item) => &item.attrs,
+            ImplItem::Method(item) => &item.attrs,
+            ImplItem::Type(item) => &item.attrs,
+            ImplItem::Macro(item) => &item.attrs,
+            ImplItem::Verbatim(_) => &[],
+        }
+    }
+
+    pub(crate) fn attrs_mut(&mut self) -> Option<&mut Vec<Attribute>> {
+        match self {
+            ImplItem::Const(item) => Some(&mut item.attrs),
+            ImplItem::Method(item) => Some(&mut item.attrs),
+            ImplItem::Type(item) => Some(&mut item.attrs),
+            ImplItem::Macro(item) => Some(&mut item.attrs),
+            ImplItem::Verbatim(_) => None,
+        }
+    }
+}
+
+/// Whether the lowering has anything to remove from the node
+pub(crate) trait HasConstSyntax {
+    fn has_const_syntax(&self, options: &Options) -> bool;
+}
+
+fn strips_attrs(attrs: &[Attribute], options: &Options) -> bool {
+    options.filter_attrs(attrs.to_vec()).len() != attrs.len()
+}
+
+impl HasConstSyntax for Generics {
+    fn has_const_syntax(&self, options: &Options) -> bool {
+        let attrs_in_params = self.params.iter().any(|param| match param {
+            GenericParam::Type(tp) => strips_attrs(&tp.attrs, options),
+            GenericParam::Lifetime(_) | GenericParam::Const(_) => false,
+        });
+        // syn::PredicateType has no room for attributes at all
+        let attrs_in_predicates = self.where_clause.iter().any(|where_clause| {
+            where_clause
+                .predicates
+                .iter()
+                .any(|predicate| match predicate {
+                    WherePredicate::Type(ty) => !ty.attrs.is_empty(),
+                    WherePredicate::Lifetime(_) | WherePredicate::Eq(_) => false,
+                })
+        });
+        self.has_const_bounds() || attrs_in_params || attrs_in_predicates
+    }
+}
+
+impl HasConstSyntax for Signature {
+    fn has_const_syntax(&self, options: &Options) -> bool {
+        self.generics.has_const_syntax(options) || self.has_const_types()
+    }
+}
+
+// Const closures, and inline const blocks below the MSRV that stabilised them, in the
+// statements that syn parsed
+struct ConstExprs {
+    inline_consts: bool,
+    found: bool,
+}
+
+impl ConstExprs {
+    fn new(options: &Options) -> Self {
+        Self {
+            inline_consts: options.predates(Version::INLINE_CONST),
+            found: false,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ConstExprs {
+    fn visit_expr_closure(&mut self, closure: &'ast ExprClosure) {
+        self.found |= Block::is_const_closure(closure);
+        visit::visit_expr_closure(self, closure);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        // syn keeps inline const blocks verbatim
+        if let Expr::Verbatim(tokens) = expr {
+            let first = tokens.clone().into_iter().next();
+            self.found |= self.inline_consts
+                && matches!(first, Some(TokenTree2::Ident(ident)) if ident == "const");
+        }
+        visit::visit_expr(self, expr);
+    }
+}
+
+impl HasConstSyntax for Block {
+    fn has_const_syntax(&self, options: &Options) -> bool {
+        self.stmts.iter().any(|stmt| match stmt {
+            Stmt::Item(item) => item.has_const_syntax(options),
+            Stmt::Other(stmt) => {
+                let mut const_exprs = ConstExprs::new(options);
+                const_exprs.visit_stmt(stmt);
+                const_exprs.found
+            }
+        })
+    }
+}
+
+impl HasConstSyntax for ImplItem {
+    fn has_const_syntax(&self, options: &Options) -> bool {
+        strips_attrs(self.attrs(), options)
+            || match self {
+                ImplItem::Method(method) => {
+                    method.sig.has_const_syntax(options) || method.block.has_const_syntax(options)
+                }
+                // Bounds on the associated types of impls are removed whatever they are
+                ImplItem::Type(ty) => {
+                    !ty.bounds.is_empty()
+                        || ty.generics.has_const_syntax(options)
+                        || ty
+                            .trailing_where_clause
+                            .as_ref()
+                            .map_or(false, WhereClause::has_const_bounds)
+                }
+                ImplItem::Const(_) | ImplItem::Macro(_) | ImplItem::Verbatim(_) => false,
+            }
+    }
+}
+
+impl HasConstSyntax for TraitItem {
+    fn has_const_syntax(&self, options: &Options) -> bool {
+        match self {
+            TraitItem::Method(method) => {
+                strips_attrs(&method.attrs, options)
+                    || method.sig.constness.is_some()
+                    || method.sig.has_const_syntax(options)
+                    || method
+                        .default
+                        .as_ref()
+                        .map_or(false, |block| block.has_const_syntax(options))
+            }
+            TraitItem::Type(ty) => {
+                strips_attrs(&ty.attrs, options)
+                    || ty.bounds.iter().any(TypeParamBound::is_const)
+                    || ty.generics.has_const_syntax(options)
+                    || ty
+                        .trailing_where_clause
+                        .as_ref()
+                        .map_or(false, WhereClause::has_const_bounds)
+            }
+            TraitItem::Const(item) => strips_attrs(&item.attrs, options),
+            TraitItem::Macro(item) => strips_attrs(&item.attrs, options),
+            TraitItem::Verbatim(_) => false,
+        }
+    }
+}
+
+impl HasConstSyntax for ItemConstImpl {
+    fn has_const_syntax(&self, options: &Options) -> bool {
+        // Only inherent impls may keep const methods, as far as their bounds allow
+        let keeps_const = |sig: &Signature| {
+            self.trait_.is_none()
+                && options.allows_const_fn(&self.generics)
+                && keeps_const(sig, options)
+        };
+        let unconsts_methods = self.items.iter().any(|item| match item {
+            ImplItem::Method(method) => method.sig.constness.is_some() && !keeps_const(&method.sig),
+            ImplItem::Const(_) | ImplItem::Type(_) | ImplItem::Macro(_) | ImplItem::Verbatim(_) => {
+                false
+            }
+        });
+        self.leading_constness.is_some()
+            || self.constness.is_some()
+            || strips_attrs(&self.attrs, options)
+            || self.generics.has_const_syntax(options)
+            || unconsts_methods
+            || self.items.iter().any(|item| item.has_const_syntax(options))
+    }
+}
+
+impl HasConstSyntax for ItemConstTrait {
+    fn has_const_syntax(&self, options: &Options) -> bool {
+        self.constness.is_some()
+            || strips_attrs(&self.attrs, options)
+            || self.supertraits.iter().any(TypeParamBound::is_const)
+            || self.generics.has_const_syntax(options)
+            || self.items.iter().any(|item| item.has_const_syntax(options))
+    }
+}
+
+impl HasConstSyntax for ItemConstFn {
+    fn has_const_syntax(&self, options: &Options) -> bool {
+        strips_attrs(&self.attrs, options)
+            || self.sig.constness.is_some() && !keeps_const(&self.sig, options)
+            || self.sig.has_const_syntax(options)
+            || self.block.has_const_syntax(options)
+    }
+}
+
+impl HasConstSyntax for ItemConstMod {
+    fn has_const_syntax(&self, options: &Options) -> bool {
+        strips_attrs(&self.attrs, options)
+            || self.items.iter().any(|item| item.has_const_syntax(options))
+    }
+}
+
+impl HasConstSyntax for Item {
+    fn has_const_syntax(&self, options: &Options) -> bool {
+        match self {
+            Item::Impl(item) => item.has_const_syntax(options),
+            Item::Trait(item) => item.has_const_syntax(options),
+            Item::Fn(item) => item.has_const_syntax(options),
+            Item::Mod(item) => item.has_const_syntax(options),
+            Item::Other(item) => {
+                let mut const_exprs = ConstExprs::new(options);
+                const_exprs.visit_item(item);
+                const_exprs.found
+            }
+        }
+    }
+}
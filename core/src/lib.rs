@@ -0,0 +1,629 @@
+//! The transformation behind the [`unconst_trait_impl`](https://docs.rs/unconst_trait_impl)
+//! macros, for use in other proc macros and in build scripts
+//!
+//! Each function takes the input of the corresponding macro, `#![unconst(...)]` header and
+//! `cfg(predicate) =>` prefix included, and returns its expansion.
+//!
+//! ```rust, ignore
+//! let tokens = quote! {
+//!     impl<T: ~const Default> const Trait for Wrapper<T> {}
+//! };
+//! let unconsted = unconst_trait_impl_core::unconst_item_impl(tokens)?;
+//! ```
+//!
+//! The `expand_*` functions take the mode of the expansion as well, and return an [`Expansion`]
+//! that keeps the items that parsed next to the errors of the others, as the macros do.
+//!
+//! The syntax tree behind the transformation is public as well. Its types mirror those of syn,
+//! but accept `~const`, `[const]`, `impl const` and the like, and print them back losslessly.
+//! The `visit`, `visit-mut` and `fold` features enable the `visit`, `visit_mut` and `fold`
+//! modules.
+use lint::Lint;
+use parse::{Errors, ParseRecover};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{ParseStream, Parser},
+    punctuated::{Pair, Punctuated},
+    token::{
+        Bang, Brace, Bracket, Comma, Const, Default as DefaultKW, For, Gt, Impl, Lt, Paren, Unsafe,
+    },
+    Abi, Attribute, BoundLifetimes, ConstParam, FnArg, Ident, ImplItemConst, ImplItemMacro, ItemFn,
+    ItemImpl, ItemMod, ItemTrait, Lifetime, LifetimeDef, Path, PredicateEq, PredicateLifetime,
+    ReturnType, Token, TraitItemConst, TraitItemMacro, Type, Variadic, Visibility,
+};
+use unconst::Unconst;
+// syn::Generics is not suitable for support of const_trait_impl and const_fn_trait_bound
+// due to the two transitive chains:
+//
+// use syn::Generics;
+// use syn::GenericParam;
+// use syn::TypeParam;
+// use syn::TypeParamBound;
+// use syn::TraitBound;
+// use syn::TraitBoundModifier;
+//
+// use syn::Generics;
+// use syn::WhereClause;
+// use syn::WherePredicate;
+// use syn::PredicateType;
+// use syn::TypeParamBound;
+//
+// use syn::ImplItem;
+// use syn::ImplItemMethod;
+// use syn::Signature;
+//
+// use syn::ImplItem;
+// use syn::ImplItemType;
+// use syn::Generics;
+//
+// TODO: track issue: <https://github.com/dtolnay/syn/issues/1130>
+
+mod file;
+#[cfg(feature = "fold")]
+pub mod fold;
+mod from;
+mod inspect;
+mod lint;
+mod options;
+mod parse;
+mod strip;
+mod to_tokens;
+mod unconst;
+#[cfg(feature = "visit")]
+pub mod visit;
+#[cfg(feature = "visit-mut")]
+pub mod visit_mut;
+
+pub use file::{unconst_file, unconst_file_edits, Edit};
+
+/// An impl block, const or inherent: `impl<T: ~const Trait> const Trait for Type<T> {...}`
+pub struct ItemConstImpl {
+    pub attrs: Vec<Attribute>,
+    // https://github.com/rust-lang/rfcs/blob/master/text/1210-impl-specialization.md
+    pub defaultness: Option<DefaultKW>,
+    /// The `const` of the newer `const impl` form
+    pub leading_constness: Option<Const>,
+    pub unsafety: Option<Unsafe>,
+    pub impl_token: Impl,
+    pub generics: Generics,
+    pub constness: Option<Const>,
+    pub trait_: Option<(Option<Bang>, Path, For)>,
+    pub self_ty: Box<Type>,
+    pub brace_token: Brace,
+    pub items: Vec<ImplItem>,
+}
+
+/// A function signature whose generics and argument types may carry const bounds
+pub struct Signature {
+    pub constness: Option<Token![const]>,
+    pub asyncness: Option<Token![async]>,
+    pub unsafety: Option<Token![unsafe]>,
+    pub abi: Option<Abi>,
+    pub fn_token: Token![fn],
+    pub ident: Ident,
+    pub generics: Generics,
+    pub paren_token: syn::token::Paren,
+    pub inputs: Punctuated<FnArg, Token![,]>,
+    pub variadic: Option<Variadic>,
+    pub output: ReturnType,
+}
+
+/// A method within an impl block
+pub struct ImplItemMethod {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub defaultness: Option<Token![default]>,
+    pub sig: Signature,
+    pub block: Block,
+}
+
+/// An associated type within an impl block, possibly bounded in the GAT form
+pub struct ImplItemType {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub defaultness: Option<Token![default]>,
+    pub type_token: Token![type],
+    pub ident: Ident,
+    pub generics: Generics,
+    pub colon_token: Option<Token![:]>,
+    pub bounds: Punctuated<TypeParamBound, Token![+]>,
+    pub ty: Option<(Token![=], Type)>,
+    /// The where clause of the newer GAT form, which follows the type
+    pub trailing_where_clause: Option<WhereClause>,
+    pub semi_token: Token![;],
+}
+
+/// An item within an impl block
+pub enum ImplItem {
+    /// An associated constant within an impl block.
+    Const(ImplItemConst),
+
+    /// A method within an impl block.
+    Method(ImplItemMethod),
+
+    /// An associated type within an impl block.
+    Type(ImplItemType),
+
+    /// A macro invocation within an impl block.
+    Macro(ImplItemMacro),
+
+    /// Tokens within an impl block not interpreted by Syn.
+    Verbatim(TokenStream2),
+    // // The following is the only supported idiom for exhaustive matching of
+    // // this enum.
+    // //
+    // //     match expr {
+    // //         ImplItem::Const(e) => {...}
+    // //         ImplItem::Method(e) => {...}
+    // //         ...
+    // //         ImplItem::Verbatim(e) => {...}
+    // //
+    // //         #[cfg(test)]
+    // //         ImplItem::__TestExhaustive(_) => unimplemented!(),
+    // //         #[cfg(not(test))]
+    // //         _ => { /* some sane fallback */ }
+    // //     }
+    // //
+    // // This way we fail your tests but don't break your library when adding
+    // // a variant. You will be notified by a test failure when a variant is
+    // // added, so that you can add code to handle it, but your library will
+    // // continue to compile and work for downstream users in the interim.
+    // //
+    // // Once `deny(reachable)` is available in rustc, ImplItem will be
+    // // reimplemented as a non_exhaustive enum.
+    // // https://github.com/rust-lang/rust/issues/44109#issuecomment-521781237
+    // #[doc(hidden)]
+    // __TestExhaustive(crate::private),
+}
+
+/// Any item accepted by `unconst!`
+pub enum Item {
+    /// A const trait or inherent impl block.
+    Impl(ItemConstImpl),
+
+    /// A trait definition, possibly marked with `#[const_trait]`.
+    Trait(ItemConstTrait),
+
+    /// A free function, possibly const.
+    Fn(ItemConstFn),
+
+    /// An inline module whose items are unconsted as well.
+    Mod(ItemConstMod),
+
+    /// Any other item, passed through unchanged.
+    Other(syn::Item),
+}
+
+// item.rs (syn 1.0.86)
+/// An inline module: `mod m {...}`
+pub struct ItemConstMod {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub mod_token: Token![mod],
+    pub ident: Ident,
+    pub brace_token: Brace,
+    pub items: Vec<Item>,
+}
+
+// stmt.rs (syn 1.0.86)
+/// A braced block of statements
+pub struct Block {
+    pub brace_token: Brace,
+    /// Const closures, which syn can't represent, are plain closures here that carry a marker
+    /// attribute in place of their `const`. The marker is not a stable part of the API:
+    /// [`Block::is_const_closure`] recognizes these closures, and a rewrite of a closure has
+    /// to keep its attributes for the `const` to be printed back.
+    pub stmts: Vec<Stmt>,
+}
+
+/// A statement within a block
+pub enum Stmt {
+    /// An impl, trait, fn or module declared within the block.
+    Item(Item),
+
+    /// Any other statement, passed through unchanged.
+    Other(syn::Stmt),
+}
+
+// item.rs (syn 1.0.86)
+/// A free function, possibly const: `const fn f<T: ~const Trait>() {...}`
+pub struct ItemConstFn {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub sig: Signature,
+    pub block: Box<Block>,
+}
+
+// item.rs (syn 1.0.86)
+/// A trait definition, possibly const: `const trait Trait {...}`
+pub struct ItemConstTrait {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    /// The `const` of the newer `const trait` form
+    pub constness: Option<Const>,
+    pub unsafety: Option<Unsafe>,
+    pub auto_token: Option<Token![auto]>,
+    pub trait_token: Token![trait],
+    pub ident: Ident,
+    pub generics: Generics,
+    pub colon_token: Option<Token![:]>,
+    pub supertraits: Punctuated<TypeParamBound, Token![+]>,
+    pub brace_token: Brace,
+    pub items: Vec<TraitItem>,
+}
+
+// item.rs (syn 1.0.86)
+/// A method within a trait definition
+pub struct TraitItemMethod {
+    pub attrs: Vec<Attribute>,
+    pub sig: Signature,
+    pub default: Option<Block>,
+    pub semi_token: Option<Token![;]>,
+}
+
+// item.rs (syn 1.0.86)
+/// An associated type within a trait definition
+pub struct TraitItemType {
+    pub attrs: Vec<Attribute>,
+    pub type_token: Token![type],
+    pub ident: Ident,
+    pub generics: Generics,
+    pub colon_token: Option<Token![:]>,
+    pub bounds: Punctuated<TypeParamBound, Token![+]>,
+    pub default: Option<(Token![=], Type)>,
+    /// The where clause of the newer GAT form, which follows the default type
+    pub trailing_where_clause: Option<WhereClause>,
+    pub semi_token: Token![;],
+}
+
+/// An item within a trait definition
+pub enum TraitItem {
+    /// An associated constant within the definition of a trait.
+    Const(TraitItemConst),
+
+    /// A trait method within the definition of a trait.
+    Method(TraitItemMethod),
+
+    /// An associated type within the definition of a trait.
+    Type(TraitItemType),
+
+    /// A macro invocation within the definition of a trait.
+    Macro(TraitItemMacro),
+
+    /// Tokens within the definition of a trait not interpreted by Syn.
+    Verbatim(TokenStream2),
+}
+
+// generics.rs (syn 1.0.86)
+/// Generic parameters and where clause whose bounds may be const
+#[derive(Default)]
+pub struct Generics {
+    pub lt_token: Option<Lt>,
+    pub params: Punctuated<GenericParam, Comma>,
+    pub gt_token: Option<Gt>,
+    pub where_clause: Option<WhereClause>,
+}
+
+// generics.rs (syn 1.0.86)
+/// A generic parameter of a function, impl, trait or associated type
+#[allow(clippy::large_enum_variant)]
+pub enum GenericParam {
+    /// A generic type parameter: `T: Into<String>`.
+    Type(TypeParam),
+
+    /// A lifetime definition: `'a: 'b + 'c + 'd`.
+    Lifetime(LifetimeDef),
+
+    /// A const generic parameter: `const LENGTH: usize`.
+    Const(ConstParam),
+}
+
+// generics.rs (syn 1.0.86)
+/// A generic type parameter: `T: ~const Into<String>`
+pub struct TypeParam {
+    pub attrs: Vec<Attribute>,
+    pub ident: Ident,
+    pub colon_token: Option<Token![:]>,
+    pub bounds: Punctuated<TypeParamBound, Token![+]>,
+    pub eq_token: Option<Token![=]>,
+    pub default: Option<Type>,
+}
+
+// generics.rs (syn 1.0.86)
+/// A trait or lifetime used as a bound
+pub enum TypeParamBound {
+    Trait(TraitBound),
+    Lifetime(Lifetime),
+}
+
+// generics.rs (syn 1.0.86)
+/// A trait used as a bound, possibly const: `~const Trait`
+pub struct TraitBound {
+    pub paren_token: Option<Paren>,
+    pub modifier: TraitBoundModifier,
+    /// The `for<'a>` in `for<'a> Foo<&'a T>`
+    pub lifetimes: Option<BoundLifetimes>,
+    /// The `Foo<&'a T>` in `for<'a> Foo<&'a T>`
+    pub path: Path,
+}
+
+// generics.rs (syn 1.0.86)
+/// The modifier of a trait bound: `?`, `~const`, `[const]` or `const`
+pub enum TraitBoundModifier {
+    None,
+    Maybe(Token![?]),
+    /// `~const Trait`, the older spelling of `[const] Trait`
+    TildeConst(TildeConst),
+    /// `[const] Trait`
+    BracketConst(BracketConst),
+    /// `const Trait`
+    Const(Token![const]),
+}
+
+/// The `~const` modifier
+pub struct TildeConst {
+    pub tilde: Token![~],
+    pub const_: Token![const],
+}
+
+/// The `[const]` modifier
+pub struct BracketConst {
+    pub bracket_token: Bracket,
+    pub const_: Token![const],
+}
+
+/// A type predicate in a `where` clause: `T: ~const Trait`
+pub struct PredicateType {
+    /// Attributes, which only the helpers of the macros may use
+    pub attrs: Vec<Attribute>,
+    /// Any lifetimes from a `for` binding
+    pub lifetimes: Option<BoundLifetimes>,
+    /// The type being bounded
+    pub bounded_ty: Type,
+    pub colon_token: Token![:],
+    /// Trait and lifetime bounds (`Clone+Send+'static`)
+    pub bounds: Punctuated<TypeParamBound, Token![+]>,
+}
+
+// generics.rs (syn 1.0.86)
+/// A single predicate in a `where` clause
+#[allow(clippy::large_enum_variant)]
+pub enum WherePredicate {
+    /// A type predicate in a `where` clause: `for<'c> Foo<'c>: Trait<'c>`.
+    Type(PredicateType),
+
+    /// A lifetime predicate in a `where` clause: `'a: 'b + 'c`.
+    Lifetime(PredicateLifetime),
+
+    /// An equality predicate in a `where` clause (unsupported).
+    Eq(PredicateEq),
+}
+
+// generics.rs (syn 1.0.86)
+/// A `where` clause whose bounds may be const
+pub struct WhereClause {
+    pub where_token: Token![where],
+    pub predicates: Punctuated<WherePredicate, Token![,]>,
+}
+
+pub(crate) struct TokensOrDefault<'a, T: 'a>(pub &'a Option<T>);
+
+/// The settings of an invocation, given in its `#![unconst(...)]` header
+pub(crate) struct Options {
+    /// The traits whose const bounds are removed altogether instead of becoming plain bounds
+    drop_bounds: Vec<Path>,
+    /// The oldest Rust version the output has to compile on, unknown unless given
+    msrv: Option<Version>,
+    /// The attributes removed from the lowered items, such as `#[const_trait]`
+    strip_attrs: Vec<Path>,
+}
+
+/// An `#[unconst(...)]` helper attribute, which overrides the lowering of what it is put on
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Helper {
+    /// Keeps what the lowering would remove: the `const` of a method, or the bounds on
+    /// the traits listed in `drop_bounds`
+    Keep,
+    /// Removes what the lowering would keep: an impl item, or the const bounds that would
+    /// become plain ones
+    Drop,
+    /// Leaves an impl item out of the lowering, as written
+    Skip,
+}
+
+/// A Rust version such as `"1.61"`
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Version {
+    major: u32,
+    minor: u32,
+}
+
+/// The optional `cfg(predicate) =>` prefix of the macro input
+pub(crate) struct CfgSwitch {
+    #[allow(dead_code)]
+    cfg_token: Ident,
+    #[allow(dead_code)]
+    paren_token: Paren,
+    predicate: TokenStream2,
+    #[allow(dead_code)]
+    fat_arrow_token: Token![=>],
+}
+
+impl CfgSwitch {
+    fn switch(&self, original: TokenStream2, unconsted: impl ToTokens) -> TokenStream2 {
+        let predicate = &self.predicate;
+        // Nightly syntax is feature-gated even in the items removed by `cfg`, so the original
+        // tokens are hidden in a macro call, which `cfg` removes before it is expanded. The
+        // macro is defined on the spot, so that the expansion doesn't depend on the path
+        // of this crate
+        quote! {
+            #[cfg(#predicate)]
+            macro_rules! __unconst_trait_impl_emit {
+                ($($item:tt)*) => { $($item)* };
+            }
+            #[cfg(#predicate)]
+            __unconst_trait_impl_emit! { #original }
+            #[cfg(not(#predicate))]
+            #unconsted
+        }
+    }
+}
+
+// The items are parsed one by one so that, after a `cfg(predicate) =>` prefix,
+// each of them can be emitted both as written and unconsted
+fn expand<T>(
+    input: ParseStream,
+    many: bool,
+    mode: Mode,
+    errors: &mut Errors,
+) -> syn::Result<TokenStream2>
+where
+    T: ParseRecover + ToTokens + Unconst + Lint,
+    T::Output: ToTokens,
+{
+    let options: Options = input.parse()?;
+    let cfg_switch = if CfgSwitch::peek(input) {
+        Some(input.parse::<CfgSwitch>()?)
+    } else {
+        None
+    };
+    let mut tokens = TokenStream2::new();
+    loop {
+        if many && input.is_empty() {
+            break;
+        }
+        // The item is emitted as written only once it has been parsed, so that both
+        // toolchains see the same validated input, short of the helper attributes.
+        // A bad item is skipped, and so are the bad items nested in the others
+        let item = if many {
+            match parse::recover::<T>(input, errors) {
+                Some(item) => item,
+                None => continue,
+            }
+        } else {
+            T::parse_recover(input, errors)?
+        };
+        // A warning is pointless while the input is being fixed, and would end up in the
+        // sources rewritten for good
+        if mode != Mode::Rewrite && errors.is_empty() {
+            tokens.extend(item.lint(&options));
+        }
+        match &cfg_switch {
+            _ if mode == Mode::Passthrough => {
+                tokens.extend(strip::helper_attrs(item.to_token_stream()))
+            }
+            Some(cfg_switch) if mode == Mode::Unconst => {
+                let original = strip::helper_attrs(item.to_token_stream());
+                tokens.extend(cfg_switch.switch(original, item.unconst(&options)))
+            }
+            _ => item.unconst(&options).to_tokens(&mut tokens),
+        }
+        if !many {
+            // The item is still emitted, so that what follows it only adds an error of its own
+            if !input.is_empty() {
+                errors.push(input.error("unexpected tokens after the item"));
+                input.parse::<TokenStream2>()?;
+            }
+            break;
+        }
+    }
+    Ok(tokens)
+}
+
+fn expand_with<T>(input: TokenStream2, many: bool, mode: Mode) -> Expansion
+where
+    T: ParseRecover + ToTokens + Unconst + Lint,
+    T::Output: ToTokens,
+{
+    let mut errors = Errors::default();
+    let parser = |input: ParseStream| expand::<T>(input, many, mode, &mut errors);
+    let tokens = parser.parse2(input).unwrap_or_else(|error| {
+        errors.push(error);
+        TokenStream2::new()
+    });
+    Expansion {
+        tokens,
+        error: errors.into_error(),
+    }
+}
+
+/// The expansion of the items that parsed, along with the errors of the others
+pub struct Expansion {
+    pub tokens: TokenStream2,
+    /// The errors of all of the items skipped, combined
+    pub error: Option<syn::Error>,
+}
+
+impl Expansion {
+    /// The expansion, unless an item was skipped
+    pub fn into_result(self) -> syn::Result<TokenStream2> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.tokens),
+        }
+    }
+}
+
+// What a proc macro emits: the items that parsed still expand next to the errors,
+// so that those that are used elsewhere don't add errors of their own
+impl ToTokens for Expansion {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.tokens.to_tokens(tokens);
+        if let Some(error) = &self.error {
+            tokens.extend(error.to_compile_error());
+        }
+    }
+}
+
+/// What the expansion does with the parsed items
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Lower them into syntax accepted on stable
+    Unconst,
+    /// Emit them as written, for toolchains that accept the nightly syntax
+    Passthrough,
+    /// Lower them for good: a `cfg(predicate) =>` prefix is ignored, so that the output no
+    /// longer refers to `unconst_trait_impl`
+    Rewrite,
+}
+
+/// Expands the input of `unconst_trait_impl!`: a const trait impl or an inherent impl
+pub fn expand_item_impl(input: TokenStream2, mode: Mode) -> Expansion {
+    expand_with::<ItemConstImpl>(input, false, mode)
+}
+
+/// Expands the input of `unconst_trait!`: the definition of a const trait
+pub fn expand_item_trait(input: TokenStream2, mode: Mode) -> Expansion {
+    expand_with::<ItemConstTrait>(input, false, mode)
+}
+
+/// Expands the input of `unconst_fn!`: a free const fn
+pub fn expand_item_fn(input: TokenStream2, mode: Mode) -> Expansion {
+    expand_with::<ItemConstFn>(input, false, mode)
+}
+
+/// Expands the input of `unconst!`: any number of items
+pub fn expand_items(input: TokenStream2, mode: Mode) -> Expansion {
+    expand_with::<Item>(input, true, mode)
+}
+
+/// Unconsts a const trait impl or an inherent impl
+pub fn unconst_item_impl(input: TokenStream2) -> syn::Result<TokenStream2> {
+    expand_item_impl(input, Mode::Unconst).into_result()
+}
+
+/// Unconsts the definition of a const trait
+pub fn unconst_item_trait(input: TokenStream2) -> syn::Result<TokenStream2> {
+    expand_item_trait(input, Mode::Unconst).into_result()
+}
+
+/// Unconsts a free const fn
+pub fn unconst_item_fn(input: TokenStream2) -> syn::Result<TokenStream2> {
+    expand_item_fn(input, Mode::Unconst).into_result()
+}
+
+/// Unconsts any number of items
+pub fn unconst_items(input: TokenStream2) -> syn::Result<TokenStream2> {
+    expand_items(input, Mode::Unconst).into_result()
+}
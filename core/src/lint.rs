@@ -0,0 +1,38 @@
+use crate::{inspect::HasConstSyntax, Item, ItemConstFn, ItemConstImpl, ItemConstTrait, Options};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote_spanned;
+
+/// Warnings about the input of a macro, emitted next to its expansion
+pub(crate) trait Lint {
+    fn lint(&self, _options: &Options) -> Option<TokenStream2> {
+        None
+    }
+}
+
+// Stable Rust has no way for a proc macro to warn, but it warns about the use of a deprecated
+// item wherever the use is spanned
+impl Lint for ItemConstImpl {
+    fn lint(&self, options: &Options) -> Option<TokenStream2> {
+        // The impl uses none of what the macro is for, so that the lowering leaves it as it is
+        if self.has_const_syntax(options) {
+            return None;
+        }
+        let span = self.impl_token.span;
+        Some(quote_spanned! {span=>
+            const _: () = {
+                #[deprecated(
+                    note = "the impl has nothing to unconst, consider removing the `unconst_trait_impl!` call around it"
+                )]
+                #[allow(non_upper_case_globals)]
+                const unnecessary_unconst_trait_impl: () = ();
+                unnecessary_unconst_trait_impl
+            };
+        })
+    }
+}
+
+impl Lint for ItemConstTrait {}
+
+impl Lint for ItemConstFn {}
+
+impl Lint for Item {}
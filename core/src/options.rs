@@ -0,0 +1,97 @@
+use crate::{Generics, Options, TypeParamBound, Version};
+use proc_macro2::Span as Span2;
+use syn::{Attribute, Ident, Path};
+
+impl Default for Options {
+    fn default() -> Self {
+        // `~const Drop` used to be the way to call destructors in const fns, and
+        // `~const Destruct` replaced it. Neither is meaningful, or even nameable, on stable
+        Self {
+            drop_bounds: vec![
+                Ident::new("Drop", Span2::call_site()).into(),
+                Ident::new("Destruct", Span2::call_site()).into(),
+            ],
+            msrv: None,
+            // Only nightly knows `#[const_trait]`, and only the lowering the helpers
+            strip_attrs: vec![
+                Ident::new("const_trait", Span2::call_site()).into(),
+                Ident::new("unconst", Span2::call_site()).into(),
+            ],
+        }
+    }
+}
+
+impl Version {
+    /// Stabilisation of trait bounds other than `?Sized` on const fns
+    pub(crate) const CONST_FN_TRAIT_BOUND: Version = Version::new(1, 61);
+    /// Stabilisation of inline `const { ... }` blocks
+    pub(crate) const INLINE_CONST: Version = Version::new(1, 79);
+
+    pub(crate) const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl Options {
+    // Without a declared MSRV, the output has to compile on any Rust version
+    pub(crate) fn supports(&self, version: Version) -> bool {
+        self.msrv.map_or(false, |msrv| msrv >= version)
+    }
+
+    // Features are only lowered away for a declared MSRV that lacks them
+    pub(crate) fn predates(&self, version: Version) -> bool {
+        self.msrv.map_or(false, |msrv| msrv < version)
+    }
+
+    /// Whether a const fn under these generics may stay const on the MSRV
+    pub(crate) fn allows_const_fn(&self, generics: &Generics) -> bool {
+        if self.supports(Version::CONST_FN_TRAIT_BOUND) {
+            !generics.has_const_bounds()
+        } else {
+            !generics.has_trait_bounds()
+        }
+    }
+
+    /// Removes the attributes that the header, or stable Rust, doesn't want in the output
+    pub(crate) fn filter_attrs(&self, attrs: Vec<Attribute>) -> Vec<Attribute> {
+        attrs
+            .into_iter()
+            .filter(|attr| {
+                !self
+                    .strip_attrs
+                    .iter()
+                    .any(|path| same_path(&attr.path, path))
+            })
+            .collect()
+    }
+
+    pub(crate) fn drops_bound(&self, bound: &TypeParamBound) -> bool {
+        match bound {
+            TypeParamBound::Trait(tb) if tb.modifier.is_const() => self
+                .drop_bounds
+                .iter()
+                .any(|dropped| names_trait(dropped, &tb.path)),
+            TypeParamBound::Trait(_) | TypeParamBound::Lifetime(_) => false,
+        }
+    }
+}
+
+// A lone identifier names the trait whatever the path to it, while a longer path has to be
+// written the same way in the bound
+fn names_trait(dropped: &Path, path: &Path) -> bool {
+    match dropped.get_ident() {
+        Some(ident) => path.segments.last().unwrap().ident == *ident,
+        None => same_path(dropped, path),
+    }
+}
+
+// syn::Path implements PartialEq only with the "extra-traits" feature
+fn same_path(lhs: &Path, rhs: &Path) -> bool {
+    lhs.leading_colon.is_some() == rhs.leading_colon.is_some()
+        && lhs.segments.len() == rhs.segments.len()
+        && lhs
+            .segments
+            .iter()
+            .zip(&rhs.segments)
+            .all(|(lhs, rhs)| lhs.ident == rhs.ident)
+}
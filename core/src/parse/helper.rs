@@ -0,0 +1,96 @@
+use crate::Helper;
+use syn::{
+    visit::{self, Visit},
+    AttrStyle, Attribute, Error, Ident, Result,
+};
+
+impl Helper {
+    /// Whether `attr` is an `#[unconst(...)]` helper rather than the `#![unconst(...)]` header
+    pub(crate) fn is(attr: &Attribute) -> bool {
+        matches!(attr.style, AttrStyle::Outer) && attr.path.is_ident("unconst")
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Helper::Keep => "keep",
+            Helper::Drop => "drop",
+            Helper::Skip => "skip",
+        }
+    }
+
+    // #[unconst(keep)], #[unconst(drop)] or #[unconst(skip)]
+    fn parse_attr(attr: &Attribute) -> Result<Helper> {
+        let ident: Ident = attr.parse_args()?;
+        if ident == "keep" {
+            Ok(Helper::Keep)
+        } else if ident == "drop" {
+            Ok(Helper::Drop)
+        } else if ident == "skip" {
+            Ok(Helper::Skip)
+        } else {
+            Err(Error::new(
+                ident.span(),
+                "expected `keep`, `drop` or `skip`",
+            ))
+        }
+    }
+
+    /// The helper among `attrs`, which has to be one of `allowed` on `what` it is put on
+    pub(crate) fn parse_attrs(
+        attrs: &[Attribute],
+        allowed: &[Helper],
+        what: &str,
+    ) -> Result<Option<Helper>> {
+        let mut found = None;
+        for attr in attrs.iter().filter(|attr| Helper::is(attr)) {
+            let helper = Helper::parse_attr(attr)?;
+            if found.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate `unconst` helper"));
+            }
+            if !allowed.contains(&helper) {
+                let message = format!("`{}` is not supported on {}", helper.name(), what);
+                return Err(Error::new_spanned(attr, message));
+            }
+            found = Some(helper);
+        }
+        Ok(found)
+    }
+
+    /// Rejects the helpers anywhere within a statement, where syn parses the attributes
+    pub(crate) fn reject_in_stmt(stmt: &syn::Stmt) -> Result<()> {
+        let mut helpers = Helpers::default();
+        helpers.visit_stmt(stmt);
+        Helper::parse_attrs(&helpers.0, &[], "statements").map(drop)
+    }
+
+    /// Rejects the helpers anywhere within an item that is passed through unchanged
+    pub(crate) fn reject_in_item(item: &syn::Item) -> Result<()> {
+        let mut helpers = Helpers::default();
+        helpers.visit_item(item);
+        Helper::parse_attrs(
+            &helpers.0,
+            &[],
+            "items other than impls, traits, fns and modules",
+        )
+        .map(drop)
+    }
+
+    /// The helper among attributes that the parser has already validated
+    pub(crate) fn of(attrs: &[Attribute]) -> Option<Helper> {
+        let attr = attrs.iter().find(|attr| Helper::is(attr))?;
+        Helper::parse_attr(attr).ok()
+    }
+}
+
+// The first helper within syntax that syn parses, where none is supported
+#[derive(Default)]
+struct Helpers(Vec<Attribute>);
+
+impl<'ast> Visit<'ast> for Helpers {
+    fn visit_attribute(&mut self, attr: &'ast Attribute) {
+        if Helper::is(attr) && self.0.is_empty() {
+            self.0.push(attr.clone());
+        }
+        visit::visit_attribute(self, attr);
+    }
+}
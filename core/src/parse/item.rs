@@ -1,24 +1,13 @@
-use crate::{ImplItem, TypeParamBound};
+use super::local::LocalParse;
+use crate::{Generics, ImplItemType, TypeParamBound, WhereClause};
 use syn::{
-    parse::{Parse, ParseBuffer, ParseStream, Result},
+    parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
-    Generics, Ident, ImplItemType, Type, Visibility,
+    Ident, Type, Visibility,
 };
 
-#[allow(dead_code)]
-struct FlexibleItemType {
-    vis: Visibility,
-    defaultness: Option<syn::token::Default>,
-    type_token: syn::token::Type,
-    ident: Ident,
-    generics: Generics,
-    colon_token: Option<syn::token::Colon>,
-    bounds: Punctuated<TypeParamBound, syn::token::Add>,
-    ty: Option<(syn::token::Eq, Type)>,
-    semi_token: syn::token::Semi,
-}
-
-impl Parse for FlexibleItemType {
+// item.rs (syn 1.0.86)
+impl Parse for ImplItemType {
     fn parse(input: ParseStream) -> Result<Self> {
         let vis: Visibility = input.parse()?;
         let defaultness: Option<syn::token::Default> = input.parse()?;
@@ -45,14 +34,20 @@ impl Parse for FlexibleItemType {
                 bounds.push_punct(input.parse::<syn::token::Add>()?);
             }
         }
-        generics.where_clause = input.parse()?;
+        generics.where_clause = Option::<WhereClause>::local_parse(input)?;
         let ty = if let Some(eq_token) = input.parse()? {
             Some((eq_token, input.parse::<Type>()?))
         } else {
             None
         };
+        let trailing_where_clause = if generics.where_clause.is_none() {
+            Option::<WhereClause>::local_parse(input)?
+        } else {
+            None
+        };
         let semi_token: syn::token::Semi = input.parse()?;
-        Ok(FlexibleItemType {
+        Ok(ImplItemType {
+            attrs: Vec::new(),
             vis,
             defaultness,
             type_token,
@@ -61,12 +56,13 @@ impl Parse for FlexibleItemType {
             colon_token,
             bounds,
             ty,
+            trailing_where_clause,
             semi_token,
         })
     }
 }
 
-pub(super) mod verbatim {
+pub(crate) mod verbatim {
     use proc_macro2::TokenStream as TokenStream2;
     use std::iter;
     use syn::parse::{ParseBuffer, ParseStream};
@@ -83,36 +79,6 @@ pub(super) mod verbatim {
     }
 }
 
-pub(super) fn parse_impl_item_type(begin: ParseBuffer, input: ParseStream) -> Result<ImplItem> {
-    let FlexibleItemType {
-        vis,
-        defaultness,
-        type_token,
-        ident,
-        generics,
-        colon_token,
-        bounds: _,
-        ty,
-        semi_token,
-    } = input.parse()?;
-    if colon_token.is_some() || ty.is_none() {
-        Ok(ImplItem::Verbatim(verbatim::between(begin, input)))
-    } else {
-        let (eq_token, ty) = ty.unwrap();
-        Ok(ImplItem::Type(ImplItemType {
-            attrs: Vec::new(),
-            vis,
-            defaultness,
-            type_token,
-            ident,
-            generics,
-            eq_token,
-            ty,
-            semi_token,
-        }))
-    }
-}
-
 pub(super) fn peek_signature(input: ParseStream) -> bool {
     let fork = input.fork();
     fork.parse::<Option<syn::token::Const>>().is_ok()
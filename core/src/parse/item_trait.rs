@@ -0,0 +1,205 @@
+use super::{
+    item::verbatim, local::LocalParse, parse_block, parse_inner, peek_signature, recover, Errors,
+    ParseRecover,
+};
+use crate::{
+    Generics, Helper, ItemConstTrait, Signature, TraitItem, TraitItemMethod, TraitItemType,
+    TypeParamBound, WhereClause,
+};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Ident, Result, Token, TraitItemConst, Type, Visibility,
+};
+
+impl Parse for ItemConstTrait {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut errors = Errors::default();
+        let item = ItemConstTrait::parse_recover(input, &mut errors)?;
+        errors.into_result(item)
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ParseRecover for ItemConstTrait {
+    fn parse_recover(input: ParseStream, errors: &mut Errors) -> Result<Self> {
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        Helper::parse_attrs(&attrs, &[], "traits")?;
+        let vis: Visibility = input.parse()?;
+        let constness: Option<Token![const]> = input.parse()?;
+        let unsafety: Option<Token![unsafe]> = input.parse()?;
+        let auto_token: Option<Token![auto]> = input.parse()?;
+        let trait_token: Token![trait] = input.parse()?;
+        let ident: Ident = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        let colon_token: Option<Token![:]> = input.parse()?;
+
+        let mut supertraits = Punctuated::new();
+        if colon_token.is_some() {
+            loop {
+                if input.peek(Token![where]) || input.peek(syn::token::Brace) {
+                    break;
+                }
+                supertraits.push_value(input.parse::<TypeParamBound>()?);
+                if input.peek(Token![where]) || input.peek(syn::token::Brace) {
+                    break;
+                }
+                supertraits.push_punct(input.parse()?);
+            }
+        }
+
+        generics.where_clause = Option::<WhereClause>::local_parse(input)?;
+
+        let content;
+        let brace_token = braced!(content in input);
+        parse_inner(&content, &mut attrs)?;
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            items.extend(recover::<TraitItem>(&content, errors));
+        }
+
+        Ok(ItemConstTrait {
+            attrs,
+            vis,
+            constness,
+            unsafety,
+            auto_token,
+            trait_token,
+            ident,
+            generics,
+            colon_token,
+            supertraits,
+            brace_token,
+            items,
+        })
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl Parse for TraitItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let begin = input.fork();
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        Helper::parse_attrs(&attrs, &[], "trait items")?;
+        let vis: Visibility = input.parse()?;
+        let defaultness: Option<Token![default]> = input.parse()?;
+        let ahead = input.fork();
+
+        let lookahead = ahead.lookahead1();
+        let mut item = if lookahead.peek(Token![fn]) || peek_signature(&ahead) {
+            input.parse().map(TraitItem::Method)
+        } else if lookahead.peek(Token![const]) {
+            ahead.parse::<Token![const]>()?;
+            let lookahead = ahead.lookahead1();
+            if lookahead.peek(Ident) || lookahead.peek(Token![_]) {
+                input.parse::<TraitItemConst>().map(TraitItem::Const)
+            } else {
+                Err(lookahead.error())
+            }
+        } else if lookahead.peek(Token![type]) {
+            input.parse().map(TraitItem::Type)
+        } else if lookahead.peek(Ident)
+            || lookahead.peek(Token![self])
+            || lookahead.peek(Token![super])
+            || lookahead.peek(Token![crate])
+            || lookahead.peek(Token![::])
+        {
+            input.parse().map(TraitItem::Macro)
+        } else {
+            Err(lookahead.error())
+        }?;
+
+        match (vis, defaultness) {
+            (Visibility::Inherited, None) => {}
+            _ => return Ok(TraitItem::Verbatim(verbatim::between(begin, input))),
+        }
+
+        let item_attrs = match &mut item {
+            TraitItem::Const(item) => &mut item.attrs,
+            TraitItem::Method(item) => &mut item.attrs,
+            TraitItem::Type(item) => &mut item.attrs,
+            TraitItem::Macro(item) => &mut item.attrs,
+            TraitItem::Verbatim(_) => return Ok(item),
+        };
+        attrs.append(item_attrs);
+        *item_attrs = attrs;
+        Ok(item)
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl Parse for TraitItemMethod {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        let sig: Signature = input.parse()?;
+
+        let lookahead = input.lookahead1();
+        let (default, semi_token) = if lookahead.peek(syn::token::Brace) {
+            let content;
+            let brace_token = braced!(content in input);
+            parse_inner(&content, &mut attrs)?;
+            (Some(parse_block(brace_token, &content)?), None)
+        } else if lookahead.peek(Token![;]) {
+            let semi_token: Token![;] = input.parse()?;
+            (None, Some(semi_token))
+        } else {
+            return Err(lookahead.error());
+        };
+
+        Ok(TraitItemMethod {
+            attrs,
+            sig,
+            default,
+            semi_token,
+        })
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl Parse for TraitItemType {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let type_token: Token![type] = input.parse()?;
+        let ident: Ident = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        let colon_token: Option<Token![:]> = input.parse()?;
+
+        let mut bounds = Punctuated::new();
+        if colon_token.is_some() {
+            while !input.peek(Token![where]) && !input.peek(Token![=]) && !input.peek(Token![;]) {
+                if !bounds.is_empty() {
+                    bounds.push_punct(input.parse()?);
+                }
+                bounds.push_value(input.parse::<TypeParamBound>()?);
+            }
+        }
+
+        generics.where_clause = Option::<WhereClause>::local_parse(input)?;
+        let default = if input.peek(Token![=]) {
+            let eq_token: Token![=] = input.parse()?;
+            let default: Type = input.parse()?;
+            Some((eq_token, default))
+        } else {
+            None
+        };
+        let trailing_where_clause = if generics.where_clause.is_none() {
+            Option::<WhereClause>::local_parse(input)?
+        } else {
+            None
+        };
+        let semi_token: Token![;] = input.parse()?;
+
+        Ok(TraitItemType {
+            attrs,
+            type_token,
+            ident,
+            generics,
+            colon_token,
+            bounds,
+            default,
+            trailing_where_clause,
+            semi_token,
+        })
+    }
+}
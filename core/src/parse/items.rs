@@ -0,0 +1,123 @@
+use super::{parse_inner, peek_signature, recover, Errors, ParseRecover};
+use crate::{Helper, Item, ItemConstFn, ItemConstImpl, ItemConstMod, ItemConstTrait};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    Attribute, Ident, ItemTraitAlias, Result, Token, Visibility,
+};
+
+enum ItemKind {
+    Impl,
+    Trait,
+    Fn,
+    Mod,
+    Other,
+}
+
+fn peek_kind(input: ParseStream) -> Result<ItemKind> {
+    let ahead = input.fork();
+    ahead.call(Attribute::parse_outer)?;
+    ahead.parse::<Visibility>()?;
+
+    let is_impl = {
+        let ahead = ahead.fork();
+        ahead.parse::<Option<Token![default]>>()?;
+        ahead.parse::<Option<Token![const]>>()?;
+        ahead.parse::<Option<Token![unsafe]>>()?;
+        ahead.peek(Token![impl])
+    };
+    let is_trait = {
+        let ahead = ahead.fork();
+        ahead.parse::<Option<Token![const]>>()?;
+        ahead.parse::<Option<Token![unsafe]>>()?;
+        ahead.parse::<Option<Token![auto]>>()?;
+        ahead.peek(Token![trait])
+    };
+    let is_mod = ahead.peek(Token![mod]) && ahead.peek3(syn::token::Brace);
+
+    Ok(if is_impl {
+        ItemKind::Impl
+    } else if is_trait && input.fork().parse::<ItemTraitAlias>().is_err() {
+        ItemKind::Trait
+    } else if peek_signature(&ahead) {
+        ItemKind::Fn
+    } else if is_mod {
+        ItemKind::Mod
+    } else {
+        ItemKind::Other
+    })
+}
+
+impl Item {
+    /// Whether the input starts with an item other than `Item::Other`
+    pub(super) fn peek(input: ParseStream) -> bool {
+        !matches!(peek_kind(input), Ok(ItemKind::Other) | Err(_))
+    }
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut errors = Errors::default();
+        let item = Item::parse_recover(input, &mut errors)?;
+        errors.into_result(item)
+    }
+}
+
+impl ParseRecover for Item {
+    fn parse_recover(input: ParseStream, errors: &mut Errors) -> Result<Self> {
+        match peek_kind(input)? {
+            ItemKind::Impl => ItemConstImpl::parse_recover(input, errors).map(Item::Impl),
+            ItemKind::Trait => ItemConstTrait::parse_recover(input, errors).map(Item::Trait),
+            ItemKind::Fn => input.parse().map(Item::Fn),
+            ItemKind::Mod => ItemConstMod::parse_recover(input, errors).map(Item::Mod),
+            ItemKind::Other => {
+                let item = input.parse()?;
+                Helper::reject_in_item(&item)?;
+                Ok(Item::Other(item))
+            }
+        }
+    }
+
+    fn salvage(input: ParseStream) -> Option<Self> {
+        match peek_kind(input) {
+            Ok(ItemKind::Fn) => ItemConstFn::salvage(input).map(Item::Fn),
+            _ => None,
+        }
+    }
+}
+
+impl Parse for ItemConstMod {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut errors = Errors::default();
+        let item = ItemConstMod::parse_recover(input, &mut errors)?;
+        errors.into_result(item)
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ParseRecover for ItemConstMod {
+    fn parse_recover(input: ParseStream, errors: &mut Errors) -> Result<Self> {
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        Helper::parse_attrs(&attrs, &[], "modules")?;
+        let vis: Visibility = input.parse()?;
+        let mod_token: Token![mod] = input.parse()?;
+        let ident: Ident = input.parse()?;
+
+        let content;
+        let brace_token = braced!(content in input);
+        parse_inner(&content, &mut attrs)?;
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            items.extend(recover::<Item>(&content, errors));
+        }
+
+        Ok(ItemConstMod {
+            attrs,
+            vis,
+            mod_token,
+            ident,
+            brace_token,
+            items,
+        })
+    }
+}
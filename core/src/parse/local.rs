@@ -21,9 +21,6 @@ pub(super) trait LocalIsInherited {
 
 impl LocalIsInherited for Visibility {
     fn local_is_inherited(&self) -> bool {
-        match *self {
-            Visibility::Inherited => true,
-            _ => false,
-        }
+        matches!(*self, Visibility::Inherited)
     }
 }
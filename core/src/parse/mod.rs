@@ -1,29 +1,39 @@
+mod helper;
 mod item;
+mod item_trait;
+mod items;
 mod local;
+mod options;
+mod recover;
+mod stmt;
+mod ty;
 
 use crate::{
-    GenericParam, Generics, ImplItem, ImplItemMethod, ItemConstImpl, PredicateLifetime,
-    PredicateType, Signature, TildeConst, TraitBound, TraitBoundModifier, TypeParam,
-    TypeParamBound, WhereClause, WherePredicate,
+    strip, Block, BracketConst, CfgSwitch, GenericParam, Generics, Helper, ImplItem,
+    ImplItemMethod, ItemConstFn, ItemConstImpl, PredicateLifetime, PredicateType, Signature, Stmt,
+    TildeConst, TraitBound, TraitBoundModifier, TypeParam, TypeParamBound, WhereClause,
+    WherePredicate,
 };
-use item::{parse_impl_item_type, peek_signature, verbatim};
+use item::peek_signature;
+pub(crate) use item::verbatim;
 use local::{LocalIsInherited, LocalParse};
 use proc_macro2::{
-    Punct, Spacing, Span as Span2, TokenStream as TokenStream2, TokenTree as TokenTree2,
+    Delimiter, Group, Punct, Spacing, TokenStream as TokenStream2, TokenTree as TokenTree2,
 };
+pub(crate) use recover::{recover, Errors, ParseRecover};
 use syn::{
     braced, bracketed,
     ext::IdentExt,
     parenthesized,
-    parse::{discouraged::Speculative, Parse, ParseStream},
+    parse::{discouraged::Speculative, Parse, ParseStream, Parser},
     parse2,
     punctuated::Punctuated,
-    spanned::Spanned,
-    token::{Bang, Brace, Default as DefaultKW, Impl, Paren, Pound},
-    Abi, AttrStyle, Attribute, Block, BoundLifetimes, ConstParam, Error, FnArg, Ident,
-    ImplItemConst, Item, Lifetime, LifetimeDef, ParenthesizedGenericArguments, Pat, PatType, Path,
-    PathArguments, Result, ReturnType, Stmt, Token, Type, TypePath, Variadic, Visibility,
+    token::{Bang, Brace, Bracket, Default as DefaultKW, Impl, Paren, Pound},
+    Abi, AttrStyle, Attribute, BoundLifetimes, ConstParam, Error, FnArg, Ident, ImplItemConst,
+    Item, Lifetime, LifetimeDef, ParenthesizedGenericArguments, Pat, PatType, Path, PathArguments,
+    Result, Token, Type, TypePath, Variadic, Visibility,
 };
+use ty::{parse_return_type, parse_type, verbatim_if_const};
 
 impl Parse for TildeConst {
     fn parse(input: ParseStream) -> Result<Self> {
@@ -34,6 +44,31 @@ impl Parse for TildeConst {
     }
 }
 
+impl BracketConst {
+    fn peek(input: ParseStream) -> bool {
+        input.peek(Bracket) && {
+            let fork = input.fork();
+            let peek_const = || -> Result<bool> {
+                let content;
+                bracketed!(content in fork);
+                content.parse::<Token![const]>()?;
+                Ok(content.is_empty())
+            };
+            peek_const().unwrap_or(false)
+        }
+    }
+}
+
+impl Parse for BracketConst {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            bracket_token: bracketed!(content in input),
+            const_: content.parse::<Token![const]>()?,
+        })
+    }
+}
+
 // generics.rs (syn 1.0.86)
 impl Parse for TraitBoundModifier {
     fn parse(input: ParseStream) -> Result<Self> {
@@ -41,6 +76,10 @@ impl Parse for TraitBoundModifier {
             input.parse().map(TraitBoundModifier::Maybe)
         } else if input.peek(Token![~]) && input.peek2(Token![const]) {
             input.parse().map(TraitBoundModifier::TildeConst)
+        } else if BracketConst::peek(input) {
+            input.parse().map(TraitBoundModifier::BracketConst)
+        } else if input.peek(Token![const]) {
+            input.parse().map(TraitBoundModifier::Const)
         } else {
             Ok(TraitBoundModifier::None)
         }
@@ -184,6 +223,38 @@ impl Parse for TypeParam {
     }
 }
 
+// The helpers weaken or keep the bounds of a type parameter, which has nothing to skip
+const TYPE_PARAM_HELPERS: &[Helper] = &[Helper::Keep, Helper::Drop];
+
+// generics.rs (syn 1.0.86)
+impl Parse for GenericParam {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let lookahead = input.lookahead1();
+        if lookahead.peek(Ident) {
+            Helper::parse_attrs(&attrs, TYPE_PARAM_HELPERS, "type parameters")?;
+            Ok(GenericParam::Type(TypeParam {
+                attrs,
+                ..input.parse()?
+            }))
+        } else if lookahead.peek(Lifetime) {
+            Helper::parse_attrs(&attrs, &[], "lifetime parameters")?;
+            Ok(GenericParam::Lifetime(LifetimeDef {
+                attrs,
+                ..input.parse()?
+            }))
+        } else if lookahead.peek(Token![const]) {
+            Helper::parse_attrs(&attrs, &[], "const parameters")?;
+            Ok(GenericParam::Const(ConstParam {
+                attrs,
+                ..input.parse()?
+            }))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
 // generics.rs (syn 1.0.86)
 impl Parse for Generics {
     fn parse(input: ParseStream) -> Result<Self> {
@@ -202,21 +273,25 @@ impl Parse for Generics {
             let attrs = input.call(Attribute::parse_outer)?;
             let lookahead = input.lookahead1();
             if lookahead.peek(Lifetime) {
+                Helper::parse_attrs(&attrs, &[], "lifetime parameters")?;
                 params.push_value(GenericParam::Lifetime(LifetimeDef {
                     attrs,
                     ..input.parse()?
                 }));
             } else if lookahead.peek(Ident) {
+                Helper::parse_attrs(&attrs, TYPE_PARAM_HELPERS, "type parameters")?;
                 params.push_value(GenericParam::Type(TypeParam {
                     attrs,
                     ..input.parse::<TypeParam>()?
                 }));
             } else if lookahead.peek(Token![const]) {
+                Helper::parse_attrs(&attrs, &[], "const parameters")?;
                 params.push_value(GenericParam::Const(ConstParam {
                     attrs,
                     ..input.parse::<ConstParam>()?
                 }));
             } else if input.peek(Token![_]) {
+                Helper::parse_attrs(&attrs, TYPE_PARAM_HELPERS, "type parameters")?;
                 params.push_value(GenericParam::Type(TypeParam {
                     attrs,
                     ident: input.call(Ident::parse_any)?,
@@ -283,7 +358,16 @@ impl Parse for WhereClause {
 // Originally, the code was generated with a macro
 impl Parse for WherePredicate {
     fn parse(input: ParseStream) -> Result<Self> {
+        // Only the helpers, which are removed from the output, may be put on predicates
+        let attrs = input.call(Attribute::parse_outer)?;
+        if let Some(attr) = attrs.iter().find(|attr| !Helper::is(attr)) {
+            return Err(Error::new_spanned(
+                attr,
+                "expected `#[unconst(...)]` as the only attribute of a where predicate",
+            ));
+        }
         if input.peek(Lifetime) && input.peek2(syn::token::Colon) {
+            Helper::parse_attrs(&attrs, &[], "lifetime predicates")?;
             Ok(WherePredicate::Lifetime(PredicateLifetime {
                 lifetime: input.parse()?,
                 colon_token: input.parse()?,
@@ -311,7 +395,9 @@ impl Parse for WherePredicate {
                 },
             }))
         } else {
+            Helper::parse_attrs(&attrs, TYPE_PARAM_HELPERS, "type predicates")?;
             Ok(WherePredicate::Type(PredicateType {
+                attrs,
                 lifetimes: input.parse()?,
                 bounded_ty: input.parse()?,
                 colon_token: input.parse()?,
@@ -343,7 +429,7 @@ impl Parse for WherePredicate {
 }
 
 // syn::attr::parsing::parse_inner (syn 1.0.86)
-#[allow(clippy::eval_order_dependence)]
+#[allow(clippy::mixed_read_write_in_expression)]
 fn single_parse_inner(input: ParseStream) -> Result<Attribute> {
     let content;
     Ok(Attribute {
@@ -364,10 +450,20 @@ fn parse_inner(input: ParseStream, attrs: &mut Vec<Attribute>) -> Result<()> {
 }
 
 impl Parse for ItemConstImpl {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut errors = Errors::default();
+        let item = ItemConstImpl::parse_recover(input, &mut errors)?;
+        errors.into_result(item)
+    }
+}
+
+impl ParseRecover for ItemConstImpl {
     // Largely based on: https://docs.rs/syn/1.0.86/src/syn/item.rs.html#2402-2407
-    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+    fn parse_recover(input: ParseStream, errors: &mut Errors) -> Result<Self> {
         let mut attrs = input.call(Attribute::parse_outer)?;
+        Helper::parse_attrs(&attrs, &[], "impls")?;
         let defaultness = input.parse::<Option<DefaultKW>>()?;
+        let leading_constness = input.parse::<Option<Token![const]>>()?;
         let unsafety = input.parse::<Option<Token![unsafe]>>()?;
         let impl_token = input.parse::<Impl>()?;
 
@@ -391,7 +487,11 @@ impl Parse for ItemConstImpl {
         // || input.peek(Token![?]) && input.peek2(Token![const]);
         let constness = if is_const_impl {
             // input.parse::<Option<Token![?]>>()?;
-            Some(input.parse::<Token![const]>()?)
+            let const_token = input.parse::<Token![const]>()?;
+            if leading_constness.is_some() {
+                return Err(Error::new(const_token.span, "duplicate `const`"));
+            }
+            Some(const_token)
         } else {
             None
         };
@@ -426,7 +526,17 @@ impl Parse for ItemConstImpl {
             }
             self_ty = input.parse::<Type>()?;
         } else {
-            return Err(Error::new(Span2::call_site(), "expected trait impl block"));
+            if let Some(const_token) = constness {
+                return Err(Error::new(
+                    const_token.span,
+                    "inherent impls cannot be `impl const`, consider `const impl`",
+                ));
+            }
+            if let Some(bang) = polarity {
+                return Err(Error::new(bang.span, "inherent impls cannot be negative"));
+            }
+            trait_ = None;
+            self_ty = first_ty;
         };
         generics.where_clause = Option::<WhereClause>::local_parse(input)?;
 
@@ -434,16 +544,35 @@ impl Parse for ItemConstImpl {
         let brace_token = braced!(content in input);
         parse_inner(&content, &mut attrs)?;
 
+        // A bad item is skipped, so that the errors of the next ones are reported as well
         let mut items = Vec::new();
         while !content.is_empty() {
-            items.push(ImplItem::parse(&content)?);
+            let mut item = match recover::<ImplItem>(&content, errors) {
+                Some(item) => item,
+                None => continue,
+            };
+            // Methods of trait impls can't be const on stable, whatever the helper says.
+            // The item is still lowered as if the helper weren't there
+            let keeps_const = Helper::of(item.attrs()) == Some(Helper::Keep);
+            if keeps_const && (is_impl_for || !matches!(item, ImplItem::Method(_))) {
+                let attr = item.attrs().iter().find(|attr| Helper::is(attr));
+                errors.push(Error::new_spanned(
+                    attr,
+                    "`keep` is only supported on methods of inherent impls",
+                ));
+                if let Some(attrs) = item.attrs_mut() {
+                    attrs.retain(|attr| !Helper::is(attr));
+                }
+            }
+            items.push(item);
         }
         if is_impl_for && trait_.is_none() {
-            Err(Error::new(is_impl_for.span(), "expected trait name"))
+            Err(Error::new(first_ty_span, "expected trait name"))
         } else {
             Ok(ItemConstImpl {
                 attrs,
                 defaultness,
+                leading_constness,
                 unsafety,
                 impl_token,
                 generics,
@@ -463,6 +592,11 @@ impl Parse for ImplItem {
     fn parse(input: ParseStream) -> Result<Self> {
         let begin = input.fork();
         let mut attrs = input.call(Attribute::parse_outer)?;
+        Helper::parse_attrs(
+            &attrs,
+            &[Helper::Keep, Helper::Drop, Helper::Skip],
+            "impl items",
+        )?;
         let ahead = input.fork();
         let vis: Visibility = ahead.parse()?;
         let mut lookahead = ahead.lookahead1();
@@ -504,7 +638,7 @@ impl Parse for ImplItem {
                 Err(lookahead.error())
             }
         } else if lookahead.peek(syn::token::Type) {
-            parse_impl_item_type(begin, input)
+            input.parse().map(ImplItem::Type)
         } else if vis.local_is_inherited()
             && defaultness.is_none()
             && (lookahead.peek(Ident)
@@ -558,6 +692,7 @@ fn parse_fn_args(input: ParseStream) -> Result<Punctuated<FnArg, syn::token::Com
     let mut has_receiver = false;
     while !input.is_empty() {
         let attrs = input.call(Attribute::parse_outer)?;
+        Helper::parse_attrs(&attrs, &[], "fn arguments")?;
         let arg = if let Some(dots) = input.parse::<Option<syn::token::Dot3>>()? {
             FnArg::Typed(PatType {
                 attrs,
@@ -566,7 +701,7 @@ fn parse_fn_args(input: ParseStream) -> Result<Punctuated<FnArg, syn::token::Com
                 ty: Box::new(Type::Verbatim(variadic_to_tokens(&dots))),
             })
         } else {
-            let mut arg: FnArg = input.parse()?;
+            let mut arg = parse_fn_arg(input)?;
             match &mut arg {
                 FnArg::Receiver(receiver) if has_receiver => {
                     return Err(Error::new(
@@ -598,6 +733,34 @@ fn parse_fn_args(input: ParseStream) -> Result<Punctuated<FnArg, syn::token::Com
     Ok(args)
 }
 
+// Typed arguments whose type syn can't parse are retried with `parse_type`
+fn parse_fn_arg(input: ParseStream) -> Result<FnArg> {
+    let ahead = input.fork();
+    let err = match ahead.parse::<FnArg>() {
+        Ok(FnArg::Typed(mut arg)) => {
+            input.advance_to(&ahead);
+            arg.ty = Box::new(verbatim_if_const(*arg.ty));
+            return Ok(FnArg::Typed(arg));
+        }
+        Ok(receiver) => {
+            input.advance_to(&ahead);
+            return Ok(receiver);
+        }
+        Err(err) => err,
+    };
+    let ahead = input.fork();
+    let pat: Pat = ahead.parse().map_err(|_| err.clone())?;
+    let colon_token: Token![:] = ahead.parse().map_err(|_| err.clone())?;
+    let ty = parse_type(&ahead)?;
+    input.advance_to(&ahead);
+    Ok(FnArg::Typed(PatType {
+        attrs: Vec::new(),
+        pat: Box::new(pat),
+        colon_token,
+        ty: Box::new(ty),
+    }))
+}
+
 fn pop_variadic(args: &mut Punctuated<FnArg, syn::token::Comma>) -> Option<Variadic> {
     let trailing_punct = args.trailing_punct();
     let last = match args.last_mut()? {
@@ -614,7 +777,7 @@ fn pop_variadic(args: &mut Punctuated<FnArg, syn::token::Comma>) -> Option<Varia
     };
     if let Pat::Verbatim(pat) = last.pat.as_ref() {
         if pat.to_string() == "..." && !trailing_punct {
-            variadic.attrs = core::mem::replace(&mut last.attrs, Vec::new());
+            variadic.attrs = core::mem::take(&mut last.attrs);
             args.pop();
         }
     }
@@ -634,7 +797,7 @@ impl Parse for Signature {
         let paren_token = parenthesized!(content in input);
         let mut inputs = parse_fn_args(&content)?;
         let variadic = pop_variadic(&mut inputs);
-        let output: ReturnType = input.parse()?;
+        let output = parse_return_type(input)?;
         generics.where_clause = Option::<WhereClause>::local_parse(input)?;
         Ok(Signature {
             constness,
@@ -652,6 +815,30 @@ impl Parse for Signature {
     }
 }
 
+// syn can't parse const closures, so their `const` is parsed as an attribute instead
+fn parse_block(brace_token: Brace, content: ParseStream) -> Result<Block> {
+    let tokens: TokenStream2 = content.parse()?;
+    // Reparsed within the braces, which an unexpected end of the block then points at
+    let mut group = Group::new(Delimiter::Brace, strip::mark_const_closures(tokens));
+    group.set_span(brace_token.span);
+    let stmts = (|input: ParseStream| {
+        let content;
+        braced!(content in input);
+        Block::parse_within(&content)
+    })
+    .parse2(TokenTree2::Group(group).into())?;
+    Ok(Block { brace_token, stmts })
+}
+
+// stmt.rs (syn 1.0.86)
+impl Parse for Block {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let brace_token = braced!(content in input);
+        parse_block(brace_token, &content)
+    }
+}
+
 impl Parse for ImplItemMethod {
     fn parse(input: ParseStream) -> Result<Self> {
         let attrs = input.call(Attribute::parse_outer)?;
@@ -665,15 +852,14 @@ impl Parse for ImplItemMethod {
                 TokenStream2::from_iter(<[_]>::into_vec(Box::new([TokenTree2::Punct(punct)])));
             Block {
                 brace_token: Brace { span: semi.span },
-                stmts: <[_]>::into_vec(Box::new([Stmt::Item(Item::Verbatim(tokens))])),
+                stmts: <[_]>::into_vec(Box::new([Stmt::Other(syn::Stmt::Item(Item::Verbatim(
+                    tokens,
+                )))])),
             }
         } else {
             let content;
             let brace_token = braced!(content in input);
-            Block {
-                brace_token,
-                stmts: content.call(Block::parse_within)?,
-            }
+            parse_block(brace_token, &content)?
         };
         Ok(ImplItemMethod {
             attrs,
@@ -684,3 +870,45 @@ impl Parse for ImplItemMethod {
         })
     }
 }
+
+// item.rs (syn 1.0.86)
+impl Parse for ItemConstFn {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        Helper::parse_attrs(&attrs, &[], "fns")?;
+        let vis: Visibility = input.parse()?;
+        let sig: Signature = input.parse()?;
+        let content;
+        let brace_token = braced!(content in input);
+        parse_inner(&content, &mut attrs)?;
+        let block = parse_block(brace_token, &content)?;
+        Ok(ItemConstFn {
+            attrs,
+            vis,
+            sig,
+            block: Box::new(block),
+        })
+    }
+}
+
+impl CfgSwitch {
+    pub(crate) fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        match fork.parse::<Ident>() {
+            Ok(ident) => ident == "cfg" && fork.peek(Paren) && fork.peek2(Token![=>]),
+            Err(_) => false,
+        }
+    }
+}
+
+impl Parse for CfgSwitch {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(CfgSwitch {
+            cfg_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            predicate: content.parse()?,
+            fat_arrow_token: input.parse()?,
+        })
+    }
+}
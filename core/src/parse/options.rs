@@ -0,0 +1,72 @@
+use super::single_parse_inner;
+use crate::{Options, Version};
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Lit, LitStr, Meta, MetaList, NestedMeta, Path, Result, Token,
+};
+
+// "1.61" or "1.61.0"
+fn parse_version(lit: &LitStr) -> Result<Version> {
+    let value = lit.value();
+    let mut parts = value.split('.').map(str::parse::<u32>);
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), None | Some(Ok(_)), None) => {
+            Ok(Version::new(major, minor))
+        }
+        _ => Err(Error::new(lit.span(), "expected version such as \"1.61\"")),
+    }
+}
+
+// drop_bounds(MyMarker, path::to::Other)
+fn paths(list: MetaList, expected: &str) -> Result<Vec<Path>> {
+    list.nested
+        .into_iter()
+        .map(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(path)) => Ok(path),
+            nested => Err(Error::new_spanned(nested, expected)),
+        })
+        .collect()
+}
+
+// #![unconst(msrv = "1.61", drop_bounds(MyMarker), strip_attrs(rustc_const_unstable))]
+impl Parse for Options {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut options = Options::default();
+        while input.peek(Token![#]) && input.peek2(Token![!]) {
+            let attr = input.call(single_parse_inner)?;
+            if !attr.path.is_ident("unconst") {
+                return Err(Error::new_spanned(
+                    attr.path,
+                    "expected `unconst` as the only inner attribute of the invocation",
+                ));
+            }
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(Error::new_spanned(meta, "expected `unconst(...)`")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("drop_bounds") => {
+                        let paths = paths(list, "expected trait path")?;
+                        options.drop_bounds.extend(paths);
+                    }
+                    NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("strip_attrs") => {
+                        let paths = paths(list, "expected attribute path")?;
+                        options.strip_attrs.extend(paths);
+                    }
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("msrv") => {
+                        match &nv.lit {
+                            Lit::Str(lit) => options.msrv = Some(parse_version(lit)?),
+                            lit => return Err(Error::new_spanned(lit, "expected version string")),
+                        }
+                    }
+                    NestedMeta::Meta(meta) => {
+                        return Err(Error::new_spanned(meta.path(), "unknown option"))
+                    }
+                    NestedMeta::Lit(lit) => return Err(Error::new_spanned(lit, "expected option")),
+                }
+            }
+        }
+        Ok(options)
+    }
+}
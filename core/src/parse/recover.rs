@@ -0,0 +1,153 @@
+use crate::{Block, ImplItem, ImplItemMethod, ItemConstFn, Signature, TraitItem, TraitItemMethod};
+use proc_macro2::{Delimiter, TokenTree as TokenTree2};
+use quote::quote_spanned;
+use syn::{
+    parse::{discouraged::Speculative, ParseStream},
+    Attribute, Error, Result,
+};
+
+/// The errors of the items that the parser skipped to go on with the next ones
+#[derive(Default)]
+pub(crate) struct Errors(Option<Error>);
+
+impl Errors {
+    pub(crate) fn push(&mut self, error: Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub(crate) fn into_error(self) -> Option<Error> {
+        self.0
+    }
+
+    /// `value` unless an item was skipped on the way
+    pub(crate) fn into_result<T>(self, value: T) -> Result<T> {
+        match self.0 {
+            Some(error) => Err(error),
+            None => Ok(value),
+        }
+    }
+}
+
+/// Parsing that skips the nested items it fails on, so that all of their errors are
+/// reported at once
+pub(crate) trait ParseRecover: Sized {
+    fn parse_recover(input: ParseStream, errors: &mut Errors) -> Result<Self>;
+
+    /// What remains of an item that failed to parse, if anything
+    fn salvage(_input: ParseStream) -> Option<Self> {
+        None
+    }
+}
+
+/// Parses the next item, or records its error and skips it
+///
+/// A skipped item may still be salvaged, so that the IDE keeps seeing what it declares
+/// while it is being typed.
+pub(crate) fn recover<T: ParseRecover>(input: ParseStream, errors: &mut Errors) -> Option<T> {
+    let ahead = input.fork();
+    match T::parse_recover(&ahead, errors) {
+        Ok(item) => {
+            input.advance_to(&ahead);
+            Some(item)
+        }
+        Err(error) => {
+            errors.push(error);
+            let salvaged = T::salvage(&input.fork());
+            skip_item(input);
+            salvaged
+        }
+    }
+}
+
+// An item ends with its `;` or with its braced body, which a stray `;` may follow
+fn skip_item(input: ParseStream) {
+    // Only moves the cursor, so it can't fail
+    let _ = input.step(|cursor| {
+        let mut rest = *cursor;
+        while let Some((tt, next)) = rest.token_tree() {
+            rest = next;
+            match tt {
+                TokenTree2::Punct(punct) if punct.as_char() == ';' => break,
+                TokenTree2::Group(group) if group.delimiter() == Delimiter::Brace => {
+                    if let Some((punct, next)) = rest.punct() {
+                        if punct.as_char() == ';' {
+                            rest = next;
+                        }
+                    }
+                    break;
+                }
+                _ => {}
+            }
+        }
+        Ok(((), rest))
+    });
+}
+
+// A fn whose signature parses, but not what follows it, gets a `todo!()` body instead
+fn todo_body(sig: &Signature) -> Option<Block> {
+    syn::parse2(quote_spanned!(sig.ident.span()=> { ::core::todo!() })).ok()
+}
+
+// These have no items of their own to recover from
+
+impl ParseRecover for ImplItem {
+    fn parse_recover(input: ParseStream, _errors: &mut Errors) -> Result<Self> {
+        input.parse()
+    }
+
+    fn salvage(input: ParseStream) -> Option<Self> {
+        let attrs = input.call(Attribute::parse_outer).ok()?;
+        let vis = input.parse().ok()?;
+        let defaultness = input.parse().ok()?;
+        let sig = input.parse().ok()?;
+        Some(ImplItem::Method(ImplItemMethod {
+            attrs,
+            vis,
+            defaultness,
+            block: todo_body(&sig)?,
+            sig,
+        }))
+    }
+}
+
+impl ParseRecover for TraitItem {
+    fn parse_recover(input: ParseStream, _errors: &mut Errors) -> Result<Self> {
+        input.parse()
+    }
+
+    fn salvage(input: ParseStream) -> Option<Self> {
+        let attrs = input.call(Attribute::parse_outer).ok()?;
+        let sig = input.parse().ok()?;
+        Some(TraitItem::Method(TraitItemMethod {
+            attrs,
+            default: Some(todo_body(&sig)?),
+            sig,
+            semi_token: None,
+        }))
+    }
+}
+
+impl ParseRecover for ItemConstFn {
+    fn parse_recover(input: ParseStream, _errors: &mut Errors) -> Result<Self> {
+        input.parse()
+    }
+
+    fn salvage(input: ParseStream) -> Option<Self> {
+        let attrs = input.call(Attribute::parse_outer).ok()?;
+        let vis = input.parse().ok()?;
+        let sig = input.parse().ok()?;
+        Some(ItemConstFn {
+            attrs,
+            vis,
+            block: Box::new(todo_body(&sig)?),
+            sig,
+        })
+    }
+}
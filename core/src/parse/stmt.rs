@@ -0,0 +1,59 @@
+use crate::{Block, Helper, Item, Stmt};
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{discouraged::Speculative, Parse, ParseStream},
+    Expr, Result, Token,
+};
+
+// stmt.rs (syn 1.0.86)
+impl Parse for Stmt {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if Item::peek(input) {
+            input.parse().map(Stmt::Item)
+        } else {
+            let stmt = input.parse()?;
+            Helper::reject_in_stmt(&stmt)?;
+            Ok(Stmt::Other(stmt))
+        }
+    }
+}
+
+// stmt.rs (syn 1.0.86)
+impl Block {
+    pub(super) fn parse_within(input: ParseStream) -> Result<Vec<Stmt>> {
+        let mut stmts = Vec::new();
+        loop {
+            while let Some(semi) = input.parse::<Option<Token![;]>>()? {
+                stmts.push(Stmt::Other(syn::Stmt::Semi(
+                    Expr::Verbatim(TokenStream2::new()),
+                    semi,
+                )));
+            }
+            if input.is_empty() {
+                break;
+            }
+            if Item::peek(input) {
+                stmts.push(Stmt::Item(input.parse()?));
+                continue;
+            }
+            // syn::Stmt doesn't accept the trailing expression of a block
+            let ahead = input.fork();
+            let stmt = match ahead.parse::<syn::Stmt>() {
+                Ok(stmt) => {
+                    input.advance_to(&ahead);
+                    stmt
+                }
+                Err(err) => {
+                    let expr: Expr = input.parse()?;
+                    if !input.is_empty() {
+                        return Err(err);
+                    }
+                    syn::Stmt::Expr(expr)
+                }
+            };
+            Helper::reject_in_stmt(&stmt)?;
+            stmts.push(Stmt::Other(stmt));
+        }
+        Ok(stmts)
+    }
+}
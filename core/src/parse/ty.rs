@@ -0,0 +1,78 @@
+use crate::strip;
+use proc_macro2::{Delimiter, Spacing, TokenStream as TokenStream2, TokenTree as TokenTree2};
+use quote::ToTokens;
+use std::iter;
+use syn::{
+    parse::{discouraged::Speculative, ParseStream},
+    parse2, Result, ReturnType, Token, Type,
+};
+
+// The tokens up to the `,`, `;`, `where` or body that ends a type, where `->` doesn't
+// close an angle bracket
+fn type_tokens(input: ParseStream) -> Result<TokenStream2> {
+    input.step(|cursor| {
+        let mut rest = *cursor;
+        let mut tokens = TokenStream2::new();
+        let mut depth = 0usize;
+        let mut after_dash = false;
+        while let Some((tt, next)) = rest.token_tree() {
+            let mut is_dash = false;
+            match &tt {
+                TokenTree2::Punct(punct) => match punct.as_char() {
+                    ',' | ';' if depth == 0 => break,
+                    '<' => depth += 1,
+                    '>' if !after_dash => depth = depth.saturating_sub(1),
+                    '-' => is_dash = punct.spacing() == Spacing::Joint,
+                    _ => {}
+                },
+                TokenTree2::Ident(ident) if depth == 0 && ident == "where" => break,
+                TokenTree2::Group(group) if depth == 0 && group.delimiter() == Delimiter::Brace => {
+                    break
+                }
+                _ => {}
+            }
+            after_dash = is_dash;
+            tokens.extend(iter::once(tt));
+            rest = next;
+        }
+        Ok((tokens, rest))
+    })
+}
+
+// syn accepts `~const Trait` as the path `const::Trait`, which must not reach the lowering
+pub(super) fn verbatim_if_const(ty: Type) -> Type {
+    let tokens = ty.to_token_stream();
+    if strip::has_const_modifiers(&tokens) {
+        Type::Verbatim(tokens)
+    } else {
+        ty
+    }
+}
+
+/// `syn::Type` which may contain `impl ~const Trait`, kept as `Type::Verbatim` in that case
+pub(super) fn parse_type(input: ParseStream) -> Result<Type> {
+    let ahead = input.fork();
+    let err = match ahead.parse::<Type>() {
+        Ok(ty) => {
+            input.advance_to(&ahead);
+            return Ok(verbatim_if_const(ty));
+        }
+        Err(err) => err,
+    };
+    let tokens = type_tokens(input)?;
+    if !strip::has_const_modifiers(&tokens) {
+        return Err(err);
+    }
+    parse2::<Type>(strip::const_modifiers(tokens.clone()))?;
+    Ok(Type::Verbatim(tokens))
+}
+
+pub(super) fn parse_return_type(input: ParseStream) -> Result<ReturnType> {
+    if input.peek(Token![->]) {
+        let arrow: Token![->] = input.parse()?;
+        let ty = parse_type(input)?;
+        Ok(ReturnType::Type(arrow, Box::new(ty)))
+    } else {
+        Ok(ReturnType::Default)
+    }
+}
@@ -0,0 +1,183 @@
+use proc_macro2::{
+    Delimiter, Group, Ident, Punct, Spacing, TokenStream as TokenStream2, TokenTree as TokenTree2,
+};
+
+// Token-level removal of the nightly syntax that syn can't represent
+
+/// Removes the `~const`, `[const]` and `const` modifiers of the bounds in `tokens`
+pub(crate) fn const_modifiers(tokens: TokenStream2) -> TokenStream2 {
+    strip_const_modifiers(tokens, &mut false)
+}
+
+pub(crate) fn has_const_modifiers(tokens: &TokenStream2) -> bool {
+    let mut found = false;
+    strip_const_modifiers(tokens.clone(), &mut found);
+    found
+}
+
+fn is_const(tt: Option<&TokenTree2>) -> bool {
+    matches!(tt, Some(TokenTree2::Ident(ident)) if ident == "const")
+}
+
+fn is_bracket_const(group: &Group) -> bool {
+    let mut inner = group.stream().into_iter();
+    group.delimiter() == Delimiter::Bracket
+        && is_const(inner.next().as_ref())
+        && inner.next().is_none()
+}
+
+// `const` is a modifier only where a bound begins: after `impl`, `dyn` or `+`
+fn begins_bound(prev: Option<&TokenTree2>) -> bool {
+    match prev {
+        Some(TokenTree2::Ident(ident)) => ident == "impl" || ident == "dyn",
+        Some(TokenTree2::Punct(punct)) => punct.as_char() == '+',
+        _ => false,
+    }
+}
+
+fn strip_const_modifiers(tokens: TokenStream2, found: &mut bool) -> TokenStream2 {
+    let mut stripped = Vec::new();
+    let mut prev: Option<TokenTree2> = None;
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        let tt = match tt {
+            TokenTree2::Punct(punct) if punct.as_char() == '~' && is_const(iter.peek()) => {
+                iter.next();
+                *found = true;
+                continue;
+            }
+            TokenTree2::Group(group) if is_bracket_const(&group) => {
+                *found = true;
+                continue;
+            }
+            TokenTree2::Ident(ident) if ident == "const" && begins_bound(prev.as_ref()) => {
+                *found = true;
+                continue;
+            }
+            TokenTree2::Group(group) => {
+                let mut stripped = Group::new(
+                    group.delimiter(),
+                    strip_const_modifiers(group.stream(), found),
+                );
+                stripped.set_span(group.span());
+                TokenTree2::Group(stripped)
+            }
+            tt => tt,
+        };
+        prev = Some(tt.clone());
+        stripped.push(tt);
+    }
+    stripped.into_iter().collect()
+}
+
+/// The attribute that stands for the `const` of a const closure, which syn can't represent
+pub(crate) const CONST_CLOSURE: &str = "__unconst_const_closure";
+
+pub(crate) fn is_const_closure_marker(attr: &syn::Attribute) -> bool {
+    attr.path.is_ident(CONST_CLOSURE)
+}
+
+fn begins_closure(tt: Option<&TokenTree2>) -> bool {
+    match tt {
+        Some(TokenTree2::Punct(punct)) => punct.as_char() == '|',
+        Some(TokenTree2::Ident(ident)) => ident == "move",
+        _ => false,
+    }
+}
+
+// `[__unconst_const_closure]`, the brackets of the marker
+fn is_const_closure_marker_group(tt: Option<&TokenTree2>) -> bool {
+    let group = match tt {
+        Some(TokenTree2::Group(group)) if group.delimiter() == Delimiter::Bracket => group,
+        _ => return false,
+    };
+    let mut inner = group.stream().into_iter();
+    matches!(inner.next(), Some(TokenTree2::Ident(ident)) if ident == CONST_CLOSURE)
+        && inner.next().is_none()
+}
+
+/// Turns the `const` of `const |x| ...` and `const move || ...` closures into a
+/// `#[__unconst_const_closure]` attribute, so that syn parses them as plain closures
+pub(crate) fn mark_const_closures(tokens: TokenStream2) -> TokenStream2 {
+    let mut marked = Vec::new();
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match tt {
+            TokenTree2::Ident(ident) if ident == "const" && begins_closure(iter.peek()) => {
+                let span = ident.span();
+                let mut pound = Punct::new('#', Spacing::Alone);
+                pound.set_span(span);
+                let marker = TokenTree2::Ident(Ident::new(CONST_CLOSURE, span));
+                let mut brackets = Group::new(Delimiter::Bracket, marker.into());
+                brackets.set_span(span);
+                marked.push(TokenTree2::Punct(pound));
+                marked.push(TokenTree2::Group(brackets));
+            }
+            TokenTree2::Group(group) => {
+                let mut inner = Group::new(group.delimiter(), mark_const_closures(group.stream()));
+                inner.set_span(group.span());
+                marked.push(TokenTree2::Group(inner));
+            }
+            tt => marked.push(tt),
+        }
+    }
+    marked.into_iter().collect()
+}
+
+/// Turns the `#[__unconst_const_closure]` attributes back into the `const` they stand for
+pub(crate) fn unmark_const_closures(tokens: TokenStream2) -> TokenStream2 {
+    let mut unmarked = Vec::new();
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match tt {
+            TokenTree2::Punct(punct)
+                if punct.as_char() == '#' && is_const_closure_marker_group(iter.peek()) =>
+            {
+                iter.next();
+                unmarked.push(TokenTree2::Ident(Ident::new("const", punct.span())));
+            }
+            TokenTree2::Group(group) => {
+                let mut inner =
+                    Group::new(group.delimiter(), unmark_const_closures(group.stream()));
+                inner.set_span(group.span());
+                unmarked.push(TokenTree2::Group(inner));
+            }
+            tt => unmarked.push(tt),
+        }
+    }
+    unmarked.into_iter().collect()
+}
+
+// `[unconst(...)]`, the brackets of a helper attribute
+fn is_helper(tt: Option<&TokenTree2>) -> bool {
+    let group = match tt {
+        Some(TokenTree2::Group(group)) if group.delimiter() == Delimiter::Bracket => group,
+        _ => return false,
+    };
+    let mut inner = group.stream().into_iter();
+    matches!(inner.next(), Some(TokenTree2::Ident(ident)) if ident == "unconst")
+        && matches!(inner.next(), Some(TokenTree2::Group(args)) if args.delimiter() == Delimiter::Parenthesis)
+        && inner.next().is_none()
+}
+
+/// Removes the `#[unconst(...)]` helper attributes, which only the lowering understands
+pub(crate) fn helper_attrs(tokens: TokenStream2) -> TokenStream2 {
+    let mut stripped = Vec::new();
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        let tt = match tt {
+            TokenTree2::Punct(punct) if punct.as_char() == '#' && is_helper(iter.peek()) => {
+                iter.next();
+                continue;
+            }
+            TokenTree2::Group(group) => {
+                let mut stripped = Group::new(group.delimiter(), helper_attrs(group.stream()));
+                stripped.set_span(group.span());
+                TokenTree2::Group(stripped)
+            }
+            tt => tt,
+        };
+        stripped.push(tt);
+    }
+    stripped.into_iter().collect()
+}
@@ -0,0 +1,263 @@
+use super::filter_attrs::FilterAttrs;
+use crate::{
+    strip, Block, ImplItem, ImplItemMethod, ImplItemType, Item, ItemConstFn, ItemConstImpl,
+    ItemConstMod, ItemConstTrait, Signature, Stmt, TokensOrDefault, TraitItem, TraitItemMethod,
+    TraitItemType,
+};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::{FnArg, Token, Type};
+
+// The printing below is lossless: unlike the lowering, it keeps the nightly syntax as written
+
+// item.rs (syn 1.0.86)
+impl ToTokens for ItemConstImpl {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(self.attrs.outer());
+        self.defaultness.to_tokens(tokens);
+        self.leading_constness.to_tokens(tokens);
+        self.unsafety.to_tokens(tokens);
+        self.impl_token.to_tokens(tokens);
+        self.generics.to_tokens(tokens);
+        self.constness.to_tokens(tokens);
+        if let Some((polarity, path, for_token)) = &self.trait_ {
+            polarity.to_tokens(tokens);
+            path.to_tokens(tokens);
+            for_token.to_tokens(tokens);
+        }
+        self.self_ty.to_tokens(tokens);
+        self.generics.where_clause.to_tokens(tokens);
+        self.brace_token.surround(tokens, |tokens| {
+            tokens.append_all(self.attrs.inner());
+            tokens.append_all(&self.items);
+        });
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ToTokens for ImplItem {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            ImplItem::Const(item) => item.to_tokens(tokens),
+            ImplItem::Method(item) => item.to_tokens(tokens),
+            ImplItem::Type(item) => item.to_tokens(tokens),
+            ImplItem::Macro(item) => item.to_tokens(tokens),
+            ImplItem::Verbatim(item) => item.to_tokens(tokens),
+        }
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ToTokens for ImplItemMethod {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(self.attrs.outer());
+        self.vis.to_tokens(tokens);
+        self.defaultness.to_tokens(tokens);
+        self.sig.to_tokens(tokens);
+        if self.block.stmts.len() == 1 {
+            if let Stmt::Other(syn::Stmt::Item(syn::Item::Verbatim(verbatim))) =
+                &self.block.stmts[0]
+            {
+                if verbatim.to_string() == ";" {
+                    verbatim.to_tokens(tokens);
+                    return;
+                }
+            }
+        }
+        self.block.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for ImplItemType {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(self.attrs.outer());
+        self.vis.to_tokens(tokens);
+        self.defaultness.to_tokens(tokens);
+        self.type_token.to_tokens(tokens);
+        self.ident.to_tokens(tokens);
+        self.generics.to_tokens(tokens);
+        if !self.bounds.is_empty() {
+            TokensOrDefault(&self.colon_token).to_tokens(tokens);
+            self.bounds.to_tokens(tokens);
+        }
+        self.generics.where_clause.to_tokens(tokens);
+        if let Some((eq_token, ty)) = &self.ty {
+            eq_token.to_tokens(tokens);
+            ty.to_tokens(tokens);
+        }
+        self.trailing_where_clause.to_tokens(tokens);
+        self.semi_token.to_tokens(tokens);
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ToTokens for Signature {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.constness.to_tokens(tokens);
+        self.asyncness.to_tokens(tokens);
+        self.unsafety.to_tokens(tokens);
+        self.abi.to_tokens(tokens);
+        self.fn_token.to_tokens(tokens);
+        self.ident.to_tokens(tokens);
+        self.generics.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.inputs.to_tokens(tokens);
+            // `args: ...` stays among the inputs while a lone `...` is moved out of them
+            let last_is_variadic = match self.inputs.last() {
+                Some(FnArg::Typed(arg)) => match arg.ty.as_ref() {
+                    Type::Verbatim(ty) => ty.to_string() == "...",
+                    _ => false,
+                },
+                _ => false,
+            };
+            if self.variadic.is_some() && !last_is_variadic {
+                if !self.inputs.empty_or_trailing() {
+                    <Token![,]>::default().to_tokens(tokens);
+                }
+                self.variadic.to_tokens(tokens);
+            }
+        });
+        self.output.to_tokens(tokens);
+        self.generics.where_clause.to_tokens(tokens);
+    }
+}
+
+impl Block {
+    // The statements as written, with the `const` of const closures back in place
+    fn stmts_to_tokens(&self, tokens: &mut TokenStream2) {
+        let stmts = &self.stmts;
+        tokens.extend(strip::unmark_const_closures(quote!(#(#stmts)*)));
+    }
+}
+
+impl ToTokens for Block {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.brace_token.surround(tokens, |tokens| {
+            self.stmts_to_tokens(tokens);
+        });
+    }
+}
+
+impl ToTokens for Stmt {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Stmt::Item(item) => item.to_tokens(tokens),
+            Stmt::Other(stmt) => stmt.to_tokens(tokens),
+        }
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ToTokens for ItemConstFn {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(self.attrs.outer());
+        self.vis.to_tokens(tokens);
+        self.sig.to_tokens(tokens);
+        self.block.brace_token.surround(tokens, |tokens| {
+            tokens.append_all(self.attrs.inner());
+            self.block.stmts_to_tokens(tokens);
+        });
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ToTokens for ItemConstTrait {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(self.attrs.outer());
+        self.vis.to_tokens(tokens);
+        self.constness.to_tokens(tokens);
+        self.unsafety.to_tokens(tokens);
+        self.auto_token.to_tokens(tokens);
+        self.trait_token.to_tokens(tokens);
+        self.ident.to_tokens(tokens);
+        self.generics.to_tokens(tokens);
+        if !self.supertraits.is_empty() {
+            TokensOrDefault(&self.colon_token).to_tokens(tokens);
+            self.supertraits.to_tokens(tokens);
+        }
+        self.generics.where_clause.to_tokens(tokens);
+        self.brace_token.surround(tokens, |tokens| {
+            tokens.append_all(self.attrs.inner());
+            tokens.append_all(&self.items);
+        });
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ToTokens for TraitItem {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            TraitItem::Const(item) => item.to_tokens(tokens),
+            TraitItem::Method(item) => item.to_tokens(tokens),
+            TraitItem::Type(item) => item.to_tokens(tokens),
+            TraitItem::Macro(item) => item.to_tokens(tokens),
+            TraitItem::Verbatim(item) => item.to_tokens(tokens),
+        }
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ToTokens for TraitItemMethod {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(self.attrs.outer());
+        self.sig.to_tokens(tokens);
+        match &self.default {
+            Some(block) => {
+                block.brace_token.surround(tokens, |tokens| {
+                    tokens.append_all(self.attrs.inner());
+                    block.stmts_to_tokens(tokens);
+                });
+            }
+            None => {
+                TokensOrDefault(&self.semi_token).to_tokens(tokens);
+            }
+        }
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ToTokens for TraitItemType {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(self.attrs.outer());
+        self.type_token.to_tokens(tokens);
+        self.ident.to_tokens(tokens);
+        self.generics.to_tokens(tokens);
+        if !self.bounds.is_empty() {
+            TokensOrDefault(&self.colon_token).to_tokens(tokens);
+            self.bounds.to_tokens(tokens);
+        }
+        self.generics.where_clause.to_tokens(tokens);
+        if let Some((eq_token, default)) = &self.default {
+            eq_token.to_tokens(tokens);
+            default.to_tokens(tokens);
+        }
+        self.trailing_where_clause.to_tokens(tokens);
+        self.semi_token.to_tokens(tokens);
+    }
+}
+
+// item.rs (syn 1.0.86)
+impl ToTokens for ItemConstMod {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(self.attrs.outer());
+        self.vis.to_tokens(tokens);
+        self.mod_token.to_tokens(tokens);
+        self.ident.to_tokens(tokens);
+        self.brace_token.surround(tokens, |tokens| {
+            tokens.append_all(self.attrs.inner());
+            tokens.append_all(&self.items);
+        });
+    }
+}
+
+impl ToTokens for Item {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Item::Impl(item) => item.to_tokens(tokens),
+            Item::Trait(item) => item.to_tokens(tokens),
+            Item::Fn(item) => item.to_tokens(tokens),
+            Item::Mod(item) => item.to_tokens(tokens),
+            Item::Other(item) => item.to_tokens(tokens),
+        }
+    }
+}
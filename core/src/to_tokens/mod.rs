@@ -1,8 +1,9 @@
 mod filter_attrs;
+mod item;
 
 use crate::{
-    Comma, GenericParam, Generics, Pair, PredicateType, TildeConst, TokensOrDefault, TraitBound,
-    TraitBoundModifier, TypeParam, TypeParamBound, WhereClause, WherePredicate,
+    BracketConst, Comma, GenericParam, Generics, Pair, PredicateType, TildeConst, TokensOrDefault,
+    TraitBound, TraitBoundModifier, TypeParam, TypeParamBound, WhereClause, WherePredicate,
 };
 use filter_attrs::FilterAttrs;
 use proc_macro2::TokenStream as TokenStream2;
@@ -13,6 +14,7 @@ use syn::Token;
 // Originally, the code was generated with a macro
 impl ToTokens for PredicateType {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.append_all(self.attrs.outer());
         self.lifetimes.to_tokens(tokens);
         self.bounded_ty.to_tokens(tokens);
         self.colon_token.to_tokens(tokens);
@@ -48,6 +50,13 @@ impl ToTokens for TildeConst {
     }
 }
 
+impl ToTokens for BracketConst {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.bracket_token
+            .surround(tokens, |tokens| self.const_.to_tokens(tokens));
+    }
+}
+
 // generics.rs (syn 1.0.86)
 // Originally, the code was generated with a macro
 impl ToTokens for TraitBoundModifier {
@@ -56,6 +65,8 @@ impl ToTokens for TraitBoundModifier {
             TraitBoundModifier::None => {}
             TraitBoundModifier::Maybe(t) => t.to_tokens(tokens),
             TraitBoundModifier::TildeConst(tilde_const) => tilde_const.to_tokens(tokens),
+            TraitBoundModifier::BracketConst(bracket_const) => bracket_const.to_tokens(tokens),
+            TraitBoundModifier::Const(const_) => const_.to_tokens(tokens),
         }
     }
 }
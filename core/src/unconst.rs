@@ -0,0 +1,690 @@
+use crate::{
+    strip, Block, GenericParam, Generics, Helper, ImplItem, ImplItemMethod, ImplItemType, Item,
+    ItemConstFn, ItemConstImpl, ItemConstMod, ItemConstTrait, ItemFn, ItemImpl, ItemMod, ItemTrait,
+    Options, PredicateType, Signature, Stmt, TraitItem, TraitItemMethod, TraitItemType, TypeParam,
+    TypeParamBound, Version, WhereClause, WherePredicate,
+};
+use proc_macro2::{Delimiter, TokenStream as TokenStream2, TokenTree as TokenTree2};
+use quote::{quote, ToTokens};
+use syn::{
+    parse2,
+    punctuated::{Pair, Punctuated},
+    token::Add,
+    visit_mut::{self, VisitMut},
+    Error, Expr, ExprBlock, ExprClosure, FnArg, Pat, ReturnType, Type,
+};
+
+/// Lowering of the const-aware syntax tree into its `syn` counterpart accepted on stable
+pub(crate) trait Unconst {
+    type Output;
+
+    fn unconst(self, options: &Options) -> Self::Output;
+}
+
+// Removing an element must not leave its separator trailing behind
+fn filter_map_punctuated<T, U, P>(
+    punctuated: Punctuated<T, P>,
+    mut f: impl FnMut(T) -> Option<U>,
+) -> Punctuated<U, P> {
+    let trailing_punct = punctuated.trailing_punct();
+    let mut pairs = punctuated
+        .into_pairs()
+        .filter_map(|pair| {
+            let (value, punct) = pair.into_tuple();
+            f(value).map(|value| Pair::new(value, punct))
+        })
+        .collect::<Vec<Pair<U, P>>>();
+    if !trailing_punct {
+        if let Some(last) = pairs.pop() {
+            pairs.push(Pair::End(last.into_value()));
+        }
+    }
+    pairs.into_iter().collect()
+}
+
+fn unconst_punctuated<T: Unconst, P>(
+    punctuated: Punctuated<T, P>,
+    options: &Options,
+) -> Punctuated<T::Output, P> {
+    filter_map_punctuated(punctuated, |value| Some(value.unconst(options)))
+}
+
+// Const bounds on the traits listed in `drop_bounds` are dropped altogether
+// while the others become plain bounds, unless a helper keeps or drops them all
+fn unconst_bounds(
+    bounds: Punctuated<TypeParamBound, Add>,
+    helper: Option<Helper>,
+    options: &Options,
+) -> Punctuated<syn::TypeParamBound, Add> {
+    filter_map_punctuated(bounds, |bound| {
+        let dropped = match helper {
+            Some(Helper::Keep) => false,
+            Some(Helper::Drop) => bound.is_const(),
+            Some(Helper::Skip) | None => options.drops_bound(&bound),
+        };
+        if dropped {
+            None
+        } else {
+            Some(bound.into())
+        }
+    })
+}
+
+impl Unconst for TypeParam {
+    type Output = syn::TypeParam;
+
+    fn unconst(self, options: &Options) -> syn::TypeParam {
+        let TypeParam {
+            attrs,
+            ident,
+            colon_token,
+            bounds,
+            eq_token,
+            default,
+        } = self;
+        let bounds = unconst_bounds(bounds, Helper::of(&attrs), options);
+        syn::TypeParam {
+            attrs: options.filter_attrs(attrs),
+            ident,
+            colon_token: colon_token.filter(|_| !bounds.is_empty()),
+            bounds,
+            eq_token,
+            default,
+        }
+    }
+}
+
+impl Unconst for PredicateType {
+    type Output = syn::PredicateType;
+
+    fn unconst(self, options: &Options) -> syn::PredicateType {
+        let PredicateType {
+            attrs,
+            lifetimes,
+            bounded_ty,
+            colon_token,
+            bounds,
+        } = self;
+        syn::PredicateType {
+            lifetimes,
+            bounded_ty,
+            colon_token,
+            bounds: unconst_bounds(bounds, Helper::of(&attrs), options),
+        }
+    }
+}
+
+impl Unconst for GenericParam {
+    type Output = syn::GenericParam;
+
+    fn unconst(self, options: &Options) -> syn::GenericParam {
+        match self {
+            GenericParam::Const(c) => syn::GenericParam::Const(c),
+            GenericParam::Lifetime(l) => syn::GenericParam::Lifetime(l),
+            GenericParam::Type(t) => syn::GenericParam::Type(t.unconst(options)),
+        }
+    }
+}
+
+impl Unconst for WherePredicate {
+    type Output = syn::WherePredicate;
+
+    fn unconst(self, options: &Options) -> syn::WherePredicate {
+        match self {
+            WherePredicate::Eq(eq) => syn::WherePredicate::Eq(eq),
+            WherePredicate::Lifetime(lt) => syn::WherePredicate::Lifetime(lt),
+            WherePredicate::Type(ty) => syn::WherePredicate::Type(ty.unconst(options)),
+        }
+    }
+}
+
+impl Unconst for WhereClause {
+    type Output = Option<syn::WhereClause>;
+
+    // Predicates left without bounds by the removal are removed as well,
+    // and so is the whole clause if none of them remains
+    fn unconst(self, options: &Options) -> Option<syn::WhereClause> {
+        let WhereClause {
+            where_token,
+            predicates,
+        } = self;
+        let predicates = filter_map_punctuated(predicates, |predicate| {
+            let had_bounds = match &predicate {
+                WherePredicate::Type(ty) => !ty.bounds.is_empty(),
+                WherePredicate::Lifetime(_) | WherePredicate::Eq(_) => true,
+            };
+            match predicate.unconst(options) {
+                syn::WherePredicate::Type(ty) if had_bounds && ty.bounds.is_empty() => None,
+                predicate => Some(predicate),
+            }
+        });
+        if predicates.is_empty() {
+            None
+        } else {
+            Some(syn::WhereClause {
+                where_token,
+                predicates,
+            })
+        }
+    }
+}
+
+impl Unconst for Generics {
+    type Output = syn::Generics;
+
+    fn unconst(self, options: &Options) -> syn::Generics {
+        let Generics {
+            lt_token,
+            params,
+            gt_token,
+            where_clause,
+        } = self;
+        syn::Generics {
+            lt_token,
+            params: unconst_punctuated(params, options),
+            gt_token,
+            where_clause: where_clause.and_then(|where_clause| where_clause.unconst(options)),
+        }
+    }
+}
+
+// syn keeps inline const blocks verbatim
+fn inline_const(tokens: &TokenStream2) -> Option<syn::Block> {
+    let mut iter = tokens.clone().into_iter();
+    match (iter.next(), iter.next(), iter.next()) {
+        (Some(TokenTree2::Ident(ident)), Some(TokenTree2::Group(group)), None)
+            if ident == "const" && group.delimiter() == Delimiter::Brace =>
+        {
+            parse2(TokenTree2::Group(group).into()).ok()
+        }
+        _ => None,
+    }
+}
+
+fn is_inline_const(expr: &Expr) -> bool {
+    matches!(expr, Expr::Verbatim(tokens) if inline_const(tokens).is_some())
+}
+
+// Turns inline const blocks into plain blocks where that keeps the meaning of the code.
+// Repeat operands and patterns need a constant, so they are reported instead
+struct InlineConsts;
+
+impl VisitMut for InlineConsts {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Repeat(repeat) if is_inline_const(&repeat.expr) => {
+                let error = Error::new_spanned(
+                    &repeat.expr,
+                    "inline const blocks can't be lowered as repeat operands below MSRV 1.79",
+                );
+                *repeat.expr = Expr::Verbatim(error.to_compile_error());
+            }
+            Expr::Verbatim(tokens) => {
+                if let Some(block) = inline_const(tokens) {
+                    *expr = Expr::Block(ExprBlock {
+                        attrs: Vec::new(),
+                        label: None,
+                        block,
+                    });
+                }
+            }
+            _ => {}
+        }
+        visit_mut::visit_expr_mut(self, expr);
+    }
+
+    fn visit_pat_mut(&mut self, pat: &mut Pat) {
+        if let Pat::Verbatim(tokens) = pat {
+            let mut iter = tokens.clone().into_iter();
+            if iter.any(|tt| matches!(tt, TokenTree2::Ident(ident) if ident == "const")) {
+                let error = Error::new_spanned(
+                    &tokens,
+                    "inline const patterns can't be lowered below MSRV 1.79",
+                );
+                *pat = Pat::Verbatim(error.to_compile_error());
+                return;
+            }
+        }
+        visit_mut::visit_pat_mut(self, pat);
+    }
+}
+
+// Const closures become plain ones once their marker is dropped
+struct ConstClosures;
+
+impl VisitMut for ConstClosures {
+    fn visit_expr_closure_mut(&mut self, closure: &mut ExprClosure) {
+        closure
+            .attrs
+            .retain(|attr| !strip::is_const_closure_marker(attr));
+        visit_mut::visit_expr_closure_mut(self, closure);
+    }
+}
+
+impl Unconst for Stmt {
+    type Output = syn::Stmt;
+
+    fn unconst(self, options: &Options) -> syn::Stmt {
+        match self {
+            Stmt::Item(item) => syn::Stmt::Item(item.unconst(options)),
+            Stmt::Other(mut stmt) => {
+                ConstClosures.visit_stmt_mut(&mut stmt);
+                stmt
+            }
+        }
+    }
+}
+
+impl Unconst for Block {
+    type Output = syn::Block;
+
+    fn unconst(self, options: &Options) -> syn::Block {
+        let Block { brace_token, stmts } = self;
+        let mut block = syn::Block {
+            brace_token,
+            stmts: stmts
+                .into_iter()
+                .map(|stmt| stmt.unconst(options))
+                .collect(),
+        };
+        if options.predates(Version::INLINE_CONST) {
+            InlineConsts.visit_block_mut(&mut block);
+        }
+        block
+    }
+}
+
+// Types with `impl ~const Trait` in them are kept verbatim by the parser
+fn unconst_type(ty: Type) -> Type {
+    match ty {
+        Type::Verbatim(tokens) => Type::Verbatim(strip::const_modifiers(tokens)),
+        ty => ty,
+    }
+}
+
+// Whether a const fn may stay const on the MSRV as far as its own signature is concerned
+pub(crate) fn keeps_const(sig: &Signature, options: &Options) -> bool {
+    options.allows_const_fn(&sig.generics) && !sig.has_const_types()
+}
+
+// `#[unconst(keep)]` vouches that the method doesn't call through its const bounds,
+// so that these only have to be allowed on a const fn as plain bounds
+fn keeps_const_as_plain(sig: &Signature, options: &Options) -> bool {
+    options.supports(Version::CONST_FN_TRAIT_BOUND)
+        || !sig.generics.has_trait_bounds() && !sig.has_const_types()
+}
+
+impl Unconst for Signature {
+    type Output = syn::Signature;
+
+    fn unconst(self, options: &Options) -> syn::Signature {
+        let Signature {
+            constness,
+            asyncness,
+            unsafety,
+            abi,
+            fn_token,
+            ident,
+            generics,
+            paren_token,
+            inputs,
+            variadic,
+            output,
+        } = self;
+        syn::Signature {
+            constness,
+            asyncness,
+            unsafety,
+            abi,
+            fn_token,
+            ident,
+            generics: generics.unconst(options),
+            paren_token,
+            inputs: filter_map_punctuated(inputs, |input| {
+                Some(match input {
+                    FnArg::Typed(mut arg) => {
+                        arg.ty = Box::new(unconst_type(*arg.ty));
+                        FnArg::Typed(arg)
+                    }
+                    receiver => receiver,
+                })
+            }),
+            variadic,
+            output: match output {
+                ReturnType::Type(arrow, ty) => ReturnType::Type(arrow, Box::new(unconst_type(*ty))),
+                ReturnType::Default => ReturnType::Default,
+            },
+        }
+    }
+}
+
+impl Unconst for ImplItemMethod {
+    type Output = syn::ImplItemMethod;
+
+    fn unconst(self, options: &Options) -> syn::ImplItemMethod {
+        let ImplItemMethod {
+            attrs,
+            vis,
+            defaultness,
+            sig,
+            block,
+        } = self;
+        syn::ImplItemMethod {
+            attrs: options.filter_attrs(attrs),
+            vis,
+            defaultness,
+            sig: sig.unconst(options),
+            block: block.unconst(options),
+        }
+    }
+}
+
+impl Unconst for ImplItemType {
+    type Output = syn::ImplItem;
+
+    fn unconst(self, options: &Options) -> syn::ImplItem {
+        let ImplItemType {
+            attrs,
+            vis,
+            defaultness,
+            type_token,
+            ident,
+            generics,
+            colon_token: _,
+            bounds: _,
+            ty,
+            trailing_where_clause,
+            semi_token,
+        } = self;
+        let attrs = options.filter_attrs(attrs);
+        // Stable rejects bounds on the associated types of impls, which have no effect anyway
+        let generics = generics.unconst(options);
+        let trailing_where_clause =
+            trailing_where_clause.and_then(|where_clause| where_clause.unconst(options));
+        match ty {
+            Some((eq_token, ty)) if trailing_where_clause.is_none() => {
+                syn::ImplItem::Type(syn::ImplItemType {
+                    attrs,
+                    vis,
+                    defaultness,
+                    type_token,
+                    ident,
+                    generics,
+                    eq_token,
+                    ty,
+                    semi_token,
+                })
+            }
+            // syn::ImplItemType has no room for a where clause after the type
+            ty => {
+                let where_clause = &generics.where_clause;
+                let (eq_token, ty) = match ty {
+                    Some((eq_token, ty)) => (Some(eq_token), Some(ty)),
+                    None => (None, None),
+                };
+                syn::ImplItem::Verbatim(quote! {
+                    #(#attrs)*
+                    #vis #defaultness #type_token #ident #generics #where_clause #eq_token #ty #trailing_where_clause #semi_token
+                })
+            }
+        }
+    }
+}
+
+impl Unconst for ImplItem {
+    type Output = syn::ImplItem;
+
+    fn unconst(self, options: &Options) -> syn::ImplItem {
+        match self {
+            ImplItem::Const(c) => syn::ImplItem::Const(syn::ImplItemConst {
+                attrs: options.filter_attrs(c.attrs),
+                ..c
+            }),
+            ImplItem::Macro(m) => syn::ImplItem::Macro(syn::ImplItemMacro {
+                attrs: options.filter_attrs(m.attrs),
+                ..m
+            }),
+            ImplItem::Method(m) => syn::ImplItem::Method(m.unconst(options)),
+            ImplItem::Type(t) => t.unconst(options),
+            ImplItem::Verbatim(v) => syn::ImplItem::Verbatim(v),
+        }
+    }
+}
+
+impl Unconst for ItemConstImpl {
+    type Output = ItemImpl;
+
+    fn unconst(self, options: &Options) -> ItemImpl {
+        let ItemConstImpl {
+            attrs,
+            defaultness,
+            leading_constness: _leading_constness,
+            unsafety,
+            impl_token,
+            generics,
+            constness: _constness,
+            trait_,
+            self_ty,
+            brace_token,
+            items,
+        } = self;
+        // Methods in trait impls can't be const on stable. Inherent impls may keep
+        // their const methods unless those rely on bounds that the MSRV doesn't support
+        let is_inherent = trait_.is_none();
+        let allows_const_fn = options.allows_const_fn(&generics);
+        let allows_plain_const_fn =
+            options.supports(Version::CONST_FN_TRAIT_BOUND) || !generics.has_trait_bounds();
+        ItemImpl {
+            attrs: options.filter_attrs(attrs),
+            defaultness,
+            unsafety,
+            impl_token,
+            generics: generics.unconst(options),
+            trait_,
+            self_ty,
+            brace_token,
+            items: items
+                .into_iter()
+                .filter_map(|item| {
+                    let helper = Helper::of(item.attrs());
+                    match item {
+                        _ if helper == Some(Helper::Drop) => None,
+                        item if helper == Some(Helper::Skip) => Some(syn::ImplItem::Verbatim(
+                            strip::helper_attrs(item.into_token_stream()),
+                        )),
+                        ImplItem::Method(mut m) => {
+                            let keeps_const = if helper == Some(Helper::Keep) {
+                                allows_plain_const_fn && keeps_const_as_plain(&m.sig, options)
+                            } else {
+                                is_inherent && allows_const_fn && keeps_const(&m.sig, options)
+                            };
+                            if !keeps_const {
+                                m.sig.constness = None;
+                            }
+                            Some(syn::ImplItem::Method(m.unconst(options)))
+                        }
+                        item => Some(item.unconst(options)),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Unconst for ItemConstFn {
+    type Output = ItemFn;
+
+    fn unconst(self, options: &Options) -> ItemFn {
+        let ItemConstFn {
+            attrs,
+            vis,
+            mut sig,
+            block,
+        } = self;
+        if !keeps_const(&sig, options) {
+            sig.constness = None;
+        }
+        ItemFn {
+            attrs: options.filter_attrs(attrs),
+            vis,
+            sig: sig.unconst(options),
+            block: Box::new(block.unconst(options)),
+        }
+    }
+}
+
+impl Unconst for TraitItemMethod {
+    type Output = syn::TraitItemMethod;
+
+    fn unconst(self, options: &Options) -> syn::TraitItemMethod {
+        let TraitItemMethod {
+            attrs,
+            sig,
+            default,
+            semi_token,
+        } = self;
+        let mut sig = sig.unconst(options);
+        // Methods of traits can't be const on stable, whether they have a default body or not
+        sig.constness = None;
+        syn::TraitItemMethod {
+            attrs: options.filter_attrs(attrs),
+            sig,
+            default: default.map(|block| block.unconst(options)),
+            semi_token,
+        }
+    }
+}
+
+impl Unconst for TraitItemType {
+    type Output = syn::TraitItemType;
+
+    fn unconst(self, options: &Options) -> syn::TraitItemType {
+        let TraitItemType {
+            attrs,
+            type_token,
+            ident,
+            mut generics,
+            colon_token,
+            bounds,
+            default,
+            trailing_where_clause,
+            semi_token,
+        } = self;
+        // syn::TraitItemType has no room for a where clause after the default type
+        if generics.where_clause.is_none() {
+            generics.where_clause = trailing_where_clause;
+        }
+        let bounds = unconst_bounds(bounds, None, options);
+        syn::TraitItemType {
+            attrs: options.filter_attrs(attrs),
+            type_token,
+            ident,
+            generics: generics.unconst(options),
+            colon_token: colon_token.filter(|_| !bounds.is_empty()),
+            bounds,
+            default,
+            semi_token,
+        }
+    }
+}
+
+impl Unconst for TraitItem {
+    type Output = syn::TraitItem;
+
+    fn unconst(self, options: &Options) -> syn::TraitItem {
+        match self {
+            TraitItem::Const(c) => syn::TraitItem::Const(syn::TraitItemConst {
+                attrs: options.filter_attrs(c.attrs),
+                ..c
+            }),
+            TraitItem::Macro(m) => syn::TraitItem::Macro(syn::TraitItemMacro {
+                attrs: options.filter_attrs(m.attrs),
+                ..m
+            }),
+            TraitItem::Method(m) => syn::TraitItem::Method(m.unconst(options)),
+            TraitItem::Type(t) => syn::TraitItem::Type(t.unconst(options)),
+            TraitItem::Verbatim(v) => syn::TraitItem::Verbatim(v),
+        }
+    }
+}
+
+impl Unconst for ItemConstTrait {
+    type Output = ItemTrait;
+
+    fn unconst(self, options: &Options) -> ItemTrait {
+        let ItemConstTrait {
+            attrs,
+            vis,
+            constness: _constness,
+            unsafety,
+            auto_token,
+            trait_token,
+            ident,
+            generics,
+            colon_token,
+            supertraits,
+            brace_token,
+            items,
+        } = self;
+        let supertraits = unconst_bounds(supertraits, None, options);
+        ItemTrait {
+            attrs: options.filter_attrs(attrs),
+            vis,
+            unsafety,
+            auto_token,
+            trait_token,
+            ident,
+            generics: generics.unconst(options),
+            colon_token: colon_token.filter(|_| !supertraits.is_empty()),
+            supertraits,
+            brace_token,
+            items: items
+                .into_iter()
+                .map(|item| item.unconst(options))
+                .collect(),
+        }
+    }
+}
+
+impl Unconst for ItemConstMod {
+    type Output = ItemMod;
+
+    fn unconst(self, options: &Options) -> ItemMod {
+        let ItemConstMod {
+            attrs,
+            vis,
+            mod_token,
+            ident,
+            brace_token,
+            items,
+        } = self;
+        ItemMod {
+            attrs: options.filter_attrs(attrs),
+            vis,
+            mod_token,
+            ident,
+            content: Some((
+                brace_token,
+                items
+                    .into_iter()
+                    .map(|item| item.unconst(options))
+                    .collect(),
+            )),
+            semi: None,
+        }
+    }
+}
+
+impl Unconst for Item {
+    type Output = syn::Item;
+
+    fn unconst(self, options: &Options) -> syn::Item {
+        match self {
+            Item::Impl(i) => syn::Item::Impl(i.unconst(options)),
+            Item::Trait(t) => syn::Item::Trait(t.unconst(options)),
+            Item::Fn(f) => syn::Item::Fn(f.unconst(options)),
+            Item::Mod(m) => syn::Item::Mod(m.unconst(options)),
+            Item::Other(item) => item,
+        }
+    }
+}
@@ -0,0 +1,453 @@
+//! Syntax tree traversal to walk a shared borrow of a const-aware syntax tree
+//!
+//! Each method of the [`Visit`] trait is a hook that can be overridden to customize the
+//! behavior when visiting the corresponding type of node. By default, every method recursively
+//! visits the substructure of the input by invoking the right visitor method of each of its
+//! fields.
+//!
+//! The nodes borrowed from syn, such as [`Type`] or [`Attribute`], are leaves: their methods do
+//! nothing by default. Use `syn::visit` to descend into them.
+//!
+//! The traversal follows `syn::visit` (syn 1.0.86) over the types of this crate.
+
+use crate::{
+    Block, BracketConst, GenericParam, Generics, ImplItem, ImplItemMethod, ImplItemType, Item,
+    ItemConstFn, ItemConstImpl, ItemConstMod, ItemConstTrait, PredicateType, Signature, Stmt,
+    TildeConst, TraitBound, TraitBoundModifier, TraitItem, TraitItemMethod, TraitItemType,
+    TypeParam, TypeParamBound, WhereClause, WherePredicate,
+};
+use syn::{
+    Abi, Attribute, BoundLifetimes, ConstParam, FnArg, Ident, ImplItemConst, ImplItemMacro,
+    Lifetime, LifetimeDef, Path, PredicateEq, PredicateLifetime, ReturnType, TraitItemConst,
+    TraitItemMacro, Type, Variadic, Visibility,
+};
+
+/// Syntax tree traversal to walk a shared borrow of a const-aware syntax tree
+///
+/// See the [module documentation] for details.
+///
+/// [module documentation]: self
+pub trait Visit<'ast> {
+    fn visit_abi(&mut self, _i: &'ast Abi) {}
+    fn visit_attribute(&mut self, _i: &'ast Attribute) {}
+    fn visit_block(&mut self, i: &'ast Block) {
+        visit_block(self, i);
+    }
+    fn visit_bound_lifetimes(&mut self, _i: &'ast BoundLifetimes) {}
+    fn visit_bracket_const(&mut self, i: &'ast BracketConst) {
+        visit_bracket_const(self, i);
+    }
+    fn visit_const_param(&mut self, _i: &'ast ConstParam) {}
+    fn visit_fn_arg(&mut self, _i: &'ast FnArg) {}
+    fn visit_generic_param(&mut self, i: &'ast GenericParam) {
+        visit_generic_param(self, i);
+    }
+    fn visit_generics(&mut self, i: &'ast Generics) {
+        visit_generics(self, i);
+    }
+    fn visit_ident(&mut self, _i: &'ast Ident) {}
+    fn visit_impl_item(&mut self, i: &'ast ImplItem) {
+        visit_impl_item(self, i);
+    }
+    fn visit_impl_item_const(&mut self, _i: &'ast ImplItemConst) {}
+    fn visit_impl_item_macro(&mut self, _i: &'ast ImplItemMacro) {}
+    fn visit_impl_item_method(&mut self, i: &'ast ImplItemMethod) {
+        visit_impl_item_method(self, i);
+    }
+    fn visit_impl_item_type(&mut self, i: &'ast ImplItemType) {
+        visit_impl_item_type(self, i);
+    }
+    fn visit_item(&mut self, i: &'ast Item) {
+        visit_item(self, i);
+    }
+    fn visit_item_const_fn(&mut self, i: &'ast ItemConstFn) {
+        visit_item_const_fn(self, i);
+    }
+    fn visit_item_const_impl(&mut self, i: &'ast ItemConstImpl) {
+        visit_item_const_impl(self, i);
+    }
+    fn visit_item_const_mod(&mut self, i: &'ast ItemConstMod) {
+        visit_item_const_mod(self, i);
+    }
+    fn visit_item_const_trait(&mut self, i: &'ast ItemConstTrait) {
+        visit_item_const_trait(self, i);
+    }
+    fn visit_lifetime(&mut self, _i: &'ast Lifetime) {}
+    fn visit_lifetime_def(&mut self, _i: &'ast LifetimeDef) {}
+    fn visit_path(&mut self, _i: &'ast Path) {}
+    fn visit_predicate_eq(&mut self, _i: &'ast PredicateEq) {}
+    fn visit_predicate_lifetime(&mut self, _i: &'ast PredicateLifetime) {}
+    fn visit_predicate_type(&mut self, i: &'ast PredicateType) {
+        visit_predicate_type(self, i);
+    }
+    fn visit_return_type(&mut self, _i: &'ast ReturnType) {}
+    fn visit_signature(&mut self, i: &'ast Signature) {
+        visit_signature(self, i);
+    }
+    fn visit_stmt(&mut self, i: &'ast Stmt) {
+        visit_stmt(self, i);
+    }
+    fn visit_syn_item(&mut self, _i: &'ast syn::Item) {}
+    fn visit_syn_stmt(&mut self, _i: &'ast syn::Stmt) {}
+    fn visit_tilde_const(&mut self, i: &'ast TildeConst) {
+        visit_tilde_const(self, i);
+    }
+    fn visit_trait_bound(&mut self, i: &'ast TraitBound) {
+        visit_trait_bound(self, i);
+    }
+    fn visit_trait_bound_modifier(&mut self, i: &'ast TraitBoundModifier) {
+        visit_trait_bound_modifier(self, i);
+    }
+    fn visit_trait_item(&mut self, i: &'ast TraitItem) {
+        visit_trait_item(self, i);
+    }
+    fn visit_trait_item_const(&mut self, _i: &'ast TraitItemConst) {}
+    fn visit_trait_item_macro(&mut self, _i: &'ast TraitItemMacro) {}
+    fn visit_trait_item_method(&mut self, i: &'ast TraitItemMethod) {
+        visit_trait_item_method(self, i);
+    }
+    fn visit_trait_item_type(&mut self, i: &'ast TraitItemType) {
+        visit_trait_item_type(self, i);
+    }
+    fn visit_type(&mut self, _i: &'ast Type) {}
+    fn visit_type_param(&mut self, i: &'ast TypeParam) {
+        visit_type_param(self, i);
+    }
+    fn visit_type_param_bound(&mut self, i: &'ast TypeParamBound) {
+        visit_type_param_bound(self, i);
+    }
+    fn visit_variadic(&mut self, _i: &'ast Variadic) {}
+    fn visit_visibility(&mut self, _i: &'ast Visibility) {}
+    fn visit_where_clause(&mut self, i: &'ast WhereClause) {
+        visit_where_clause(self, i);
+    }
+    fn visit_where_predicate(&mut self, i: &'ast WherePredicate) {
+        visit_where_predicate(self, i);
+    }
+}
+
+pub fn visit_block<'ast, V>(v: &mut V, node: &'ast Block)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.stmts {
+        v.visit_stmt(it);
+    }
+}
+
+pub fn visit_bracket_const<'ast, V>(v: &mut V, node: &'ast BracketConst)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    let _ = (v, node);
+}
+
+pub fn visit_generic_param<'ast, V>(v: &mut V, node: &'ast GenericParam)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        GenericParam::Type(it) => v.visit_type_param(it),
+        GenericParam::Lifetime(it) => v.visit_lifetime_def(it),
+        GenericParam::Const(it) => v.visit_const_param(it),
+    }
+}
+
+pub fn visit_generics<'ast, V>(v: &mut V, node: &'ast Generics)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.params {
+        v.visit_generic_param(it);
+    }
+    if let Some(it) = &node.where_clause {
+        v.visit_where_clause(it);
+    }
+}
+
+pub fn visit_impl_item<'ast, V>(v: &mut V, node: &'ast ImplItem)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        ImplItem::Const(it) => v.visit_impl_item_const(it),
+        ImplItem::Method(it) => v.visit_impl_item_method(it),
+        ImplItem::Type(it) => v.visit_impl_item_type(it),
+        ImplItem::Macro(it) => v.visit_impl_item_macro(it),
+        ImplItem::Verbatim(_) => {}
+    }
+}
+
+pub fn visit_impl_item_method<'ast, V>(v: &mut V, node: &'ast ImplItemMethod)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_visibility(&node.vis);
+    v.visit_signature(&node.sig);
+    v.visit_block(&node.block);
+}
+
+pub fn visit_impl_item_type<'ast, V>(v: &mut V, node: &'ast ImplItemType)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    v.visit_generics(&node.generics);
+    for it in &node.bounds {
+        v.visit_type_param_bound(it);
+    }
+    if let Some((_, ty)) = &node.ty {
+        v.visit_type(ty);
+    }
+    if let Some(it) = &node.trailing_where_clause {
+        v.visit_where_clause(it);
+    }
+}
+
+pub fn visit_item<'ast, V>(v: &mut V, node: &'ast Item)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        Item::Impl(it) => v.visit_item_const_impl(it),
+        Item::Trait(it) => v.visit_item_const_trait(it),
+        Item::Fn(it) => v.visit_item_const_fn(it),
+        Item::Mod(it) => v.visit_item_const_mod(it),
+        Item::Other(it) => v.visit_syn_item(it),
+    }
+}
+
+pub fn visit_item_const_fn<'ast, V>(v: &mut V, node: &'ast ItemConstFn)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_visibility(&node.vis);
+    v.visit_signature(&node.sig);
+    v.visit_block(&node.block);
+}
+
+pub fn visit_item_const_impl<'ast, V>(v: &mut V, node: &'ast ItemConstImpl)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_generics(&node.generics);
+    if let Some((_, path, _)) = &node.trait_ {
+        v.visit_path(path);
+    }
+    v.visit_type(&node.self_ty);
+    for it in &node.items {
+        v.visit_impl_item(it);
+    }
+}
+
+pub fn visit_item_const_mod<'ast, V>(v: &mut V, node: &'ast ItemConstMod)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    for it in &node.items {
+        v.visit_item(it);
+    }
+}
+
+pub fn visit_item_const_trait<'ast, V>(v: &mut V, node: &'ast ItemConstTrait)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_visibility(&node.vis);
+    v.visit_ident(&node.ident);
+    v.visit_generics(&node.generics);
+    for it in &node.supertraits {
+        v.visit_type_param_bound(it);
+    }
+    for it in &node.items {
+        v.visit_trait_item(it);
+    }
+}
+
+pub fn visit_predicate_type<'ast, V>(v: &mut V, node: &'ast PredicateType)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    if let Some(it) = &node.lifetimes {
+        v.visit_bound_lifetimes(it);
+    }
+    v.visit_type(&node.bounded_ty);
+    for it in &node.bounds {
+        v.visit_type_param_bound(it);
+    }
+}
+
+pub fn visit_signature<'ast, V>(v: &mut V, node: &'ast Signature)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    if let Some(it) = &node.abi {
+        v.visit_abi(it);
+    }
+    v.visit_ident(&node.ident);
+    v.visit_generics(&node.generics);
+    for it in &node.inputs {
+        v.visit_fn_arg(it);
+    }
+    if let Some(it) = &node.variadic {
+        v.visit_variadic(it);
+    }
+    v.visit_return_type(&node.output);
+}
+
+pub fn visit_stmt<'ast, V>(v: &mut V, node: &'ast Stmt)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        Stmt::Item(it) => v.visit_item(it),
+        Stmt::Other(it) => v.visit_syn_stmt(it),
+    }
+}
+
+pub fn visit_tilde_const<'ast, V>(v: &mut V, node: &'ast TildeConst)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    let _ = (v, node);
+}
+
+pub fn visit_trait_bound<'ast, V>(v: &mut V, node: &'ast TraitBound)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_trait_bound_modifier(&node.modifier);
+    if let Some(it) = &node.lifetimes {
+        v.visit_bound_lifetimes(it);
+    }
+    v.visit_path(&node.path);
+}
+
+pub fn visit_trait_bound_modifier<'ast, V>(v: &mut V, node: &'ast TraitBoundModifier)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        TraitBoundModifier::None => {}
+        TraitBoundModifier::Maybe(_) => {}
+        TraitBoundModifier::TildeConst(it) => v.visit_tilde_const(it),
+        TraitBoundModifier::BracketConst(it) => v.visit_bracket_const(it),
+        TraitBoundModifier::Const(_) => {}
+    }
+}
+
+pub fn visit_trait_item<'ast, V>(v: &mut V, node: &'ast TraitItem)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        TraitItem::Const(it) => v.visit_trait_item_const(it),
+        TraitItem::Method(it) => v.visit_trait_item_method(it),
+        TraitItem::Type(it) => v.visit_trait_item_type(it),
+        TraitItem::Macro(it) => v.visit_trait_item_macro(it),
+        TraitItem::Verbatim(_) => {}
+    }
+}
+
+pub fn visit_trait_item_method<'ast, V>(v: &mut V, node: &'ast TraitItemMethod)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_signature(&node.sig);
+    if let Some(it) = &node.default {
+        v.visit_block(it);
+    }
+}
+
+pub fn visit_trait_item_type<'ast, V>(v: &mut V, node: &'ast TraitItemType)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_ident(&node.ident);
+    v.visit_generics(&node.generics);
+    for it in &node.bounds {
+        v.visit_type_param_bound(it);
+    }
+    if let Some((_, ty)) = &node.default {
+        v.visit_type(ty);
+    }
+    if let Some(it) = &node.trailing_where_clause {
+        v.visit_where_clause(it);
+    }
+}
+
+pub fn visit_type_param<'ast, V>(v: &mut V, node: &'ast TypeParam)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_attribute(it);
+    }
+    v.visit_ident(&node.ident);
+    for it in &node.bounds {
+        v.visit_type_param_bound(it);
+    }
+    if let Some(it) = &node.default {
+        v.visit_type(it);
+    }
+}
+
+pub fn visit_type_param_bound<'ast, V>(v: &mut V, node: &'ast TypeParamBound)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        TypeParamBound::Trait(it) => v.visit_trait_bound(it),
+        TypeParamBound::Lifetime(it) => v.visit_lifetime(it),
+    }
+}
+
+pub fn visit_where_clause<'ast, V>(v: &mut V, node: &'ast WhereClause)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.predicates {
+        v.visit_where_predicate(it);
+    }
+}
+
+pub fn visit_where_predicate<'ast, V>(v: &mut V, node: &'ast WherePredicate)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        WherePredicate::Type(it) => v.visit_predicate_type(it),
+        WherePredicate::Lifetime(it) => v.visit_predicate_lifetime(it),
+        WherePredicate::Eq(it) => v.visit_predicate_eq(it),
+    }
+}
@@ -0,0 +1,453 @@
+//! Syntax tree traversal to mutate an exclusive borrow of a const-aware syntax tree in place
+//!
+//! Each method of the [`VisitMut`] trait is a hook that can be overridden to customize the
+//! behavior when mutating the corresponding type of node. By default, every method recursively
+//! visits the substructure of the input by invoking the right visitor method of each of its
+//! fields.
+//!
+//! The nodes borrowed from syn, such as [`Type`] or [`Attribute`], are leaves: their methods do
+//! nothing by default. Use `syn::visit_mut` to descend into them.
+//!
+//! The traversal follows `syn::visit_mut` (syn 1.0.86) over the types of this crate.
+
+use crate::{
+    Block, BracketConst, GenericParam, Generics, ImplItem, ImplItemMethod, ImplItemType, Item,
+    ItemConstFn, ItemConstImpl, ItemConstMod, ItemConstTrait, PredicateType, Signature, Stmt,
+    TildeConst, TraitBound, TraitBoundModifier, TraitItem, TraitItemMethod, TraitItemType,
+    TypeParam, TypeParamBound, WhereClause, WherePredicate,
+};
+use syn::{
+    Abi, Attribute, BoundLifetimes, ConstParam, FnArg, Ident, ImplItemConst, ImplItemMacro,
+    Lifetime, LifetimeDef, Path, PredicateEq, PredicateLifetime, ReturnType, TraitItemConst,
+    TraitItemMacro, Type, Variadic, Visibility,
+};
+
+/// Syntax tree traversal to mutate an exclusive borrow of a const-aware syntax tree in place
+///
+/// See the [module documentation] for details.
+///
+/// [module documentation]: self
+pub trait VisitMut {
+    fn visit_abi_mut(&mut self, _i: &mut Abi) {}
+    fn visit_attribute_mut(&mut self, _i: &mut Attribute) {}
+    fn visit_block_mut(&mut self, i: &mut Block) {
+        visit_block_mut(self, i);
+    }
+    fn visit_bound_lifetimes_mut(&mut self, _i: &mut BoundLifetimes) {}
+    fn visit_bracket_const_mut(&mut self, i: &mut BracketConst) {
+        visit_bracket_const_mut(self, i);
+    }
+    fn visit_const_param_mut(&mut self, _i: &mut ConstParam) {}
+    fn visit_fn_arg_mut(&mut self, _i: &mut FnArg) {}
+    fn visit_generic_param_mut(&mut self, i: &mut GenericParam) {
+        visit_generic_param_mut(self, i);
+    }
+    fn visit_generics_mut(&mut self, i: &mut Generics) {
+        visit_generics_mut(self, i);
+    }
+    fn visit_ident_mut(&mut self, _i: &mut Ident) {}
+    fn visit_impl_item_mut(&mut self, i: &mut ImplItem) {
+        visit_impl_item_mut(self, i);
+    }
+    fn visit_impl_item_const_mut(&mut self, _i: &mut ImplItemConst) {}
+    fn visit_impl_item_macro_mut(&mut self, _i: &mut ImplItemMacro) {}
+    fn visit_impl_item_method_mut(&mut self, i: &mut ImplItemMethod) {
+        visit_impl_item_method_mut(self, i);
+    }
+    fn visit_impl_item_type_mut(&mut self, i: &mut ImplItemType) {
+        visit_impl_item_type_mut(self, i);
+    }
+    fn visit_item_mut(&mut self, i: &mut Item) {
+        visit_item_mut(self, i);
+    }
+    fn visit_item_const_fn_mut(&mut self, i: &mut ItemConstFn) {
+        visit_item_const_fn_mut(self, i);
+    }
+    fn visit_item_const_impl_mut(&mut self, i: &mut ItemConstImpl) {
+        visit_item_const_impl_mut(self, i);
+    }
+    fn visit_item_const_mod_mut(&mut self, i: &mut ItemConstMod) {
+        visit_item_const_mod_mut(self, i);
+    }
+    fn visit_item_const_trait_mut(&mut self, i: &mut ItemConstTrait) {
+        visit_item_const_trait_mut(self, i);
+    }
+    fn visit_lifetime_mut(&mut self, _i: &mut Lifetime) {}
+    fn visit_lifetime_def_mut(&mut self, _i: &mut LifetimeDef) {}
+    fn visit_path_mut(&mut self, _i: &mut Path) {}
+    fn visit_predicate_eq_mut(&mut self, _i: &mut PredicateEq) {}
+    fn visit_predicate_lifetime_mut(&mut self, _i: &mut PredicateLifetime) {}
+    fn visit_predicate_type_mut(&mut self, i: &mut PredicateType) {
+        visit_predicate_type_mut(self, i);
+    }
+    fn visit_return_type_mut(&mut self, _i: &mut ReturnType) {}
+    fn visit_signature_mut(&mut self, i: &mut Signature) {
+        visit_signature_mut(self, i);
+    }
+    fn visit_stmt_mut(&mut self, i: &mut Stmt) {
+        visit_stmt_mut(self, i);
+    }
+    fn visit_syn_item_mut(&mut self, _i: &mut syn::Item) {}
+    fn visit_syn_stmt_mut(&mut self, _i: &mut syn::Stmt) {}
+    fn visit_tilde_const_mut(&mut self, i: &mut TildeConst) {
+        visit_tilde_const_mut(self, i);
+    }
+    fn visit_trait_bound_mut(&mut self, i: &mut TraitBound) {
+        visit_trait_bound_mut(self, i);
+    }
+    fn visit_trait_bound_modifier_mut(&mut self, i: &mut TraitBoundModifier) {
+        visit_trait_bound_modifier_mut(self, i);
+    }
+    fn visit_trait_item_mut(&mut self, i: &mut TraitItem) {
+        visit_trait_item_mut(self, i);
+    }
+    fn visit_trait_item_const_mut(&mut self, _i: &mut TraitItemConst) {}
+    fn visit_trait_item_macro_mut(&mut self, _i: &mut TraitItemMacro) {}
+    fn visit_trait_item_method_mut(&mut self, i: &mut TraitItemMethod) {
+        visit_trait_item_method_mut(self, i);
+    }
+    fn visit_trait_item_type_mut(&mut self, i: &mut TraitItemType) {
+        visit_trait_item_type_mut(self, i);
+    }
+    fn visit_type_mut(&mut self, _i: &mut Type) {}
+    fn visit_type_param_mut(&mut self, i: &mut TypeParam) {
+        visit_type_param_mut(self, i);
+    }
+    fn visit_type_param_bound_mut(&mut self, i: &mut TypeParamBound) {
+        visit_type_param_bound_mut(self, i);
+    }
+    fn visit_variadic_mut(&mut self, _i: &mut Variadic) {}
+    fn visit_visibility_mut(&mut self, _i: &mut Visibility) {}
+    fn visit_where_clause_mut(&mut self, i: &mut WhereClause) {
+        visit_where_clause_mut(self, i);
+    }
+    fn visit_where_predicate_mut(&mut self, i: &mut WherePredicate) {
+        visit_where_predicate_mut(self, i);
+    }
+}
+
+pub fn visit_block_mut<V>(v: &mut V, node: &mut Block)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.stmts {
+        v.visit_stmt_mut(it);
+    }
+}
+
+pub fn visit_bracket_const_mut<V>(v: &mut V, node: &mut BracketConst)
+where
+    V: VisitMut + ?Sized,
+{
+    let _ = (v, node);
+}
+
+pub fn visit_generic_param_mut<V>(v: &mut V, node: &mut GenericParam)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        GenericParam::Type(it) => v.visit_type_param_mut(it),
+        GenericParam::Lifetime(it) => v.visit_lifetime_def_mut(it),
+        GenericParam::Const(it) => v.visit_const_param_mut(it),
+    }
+}
+
+pub fn visit_generics_mut<V>(v: &mut V, node: &mut Generics)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.params {
+        v.visit_generic_param_mut(it);
+    }
+    if let Some(it) = &mut node.where_clause {
+        v.visit_where_clause_mut(it);
+    }
+}
+
+pub fn visit_impl_item_mut<V>(v: &mut V, node: &mut ImplItem)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        ImplItem::Const(it) => v.visit_impl_item_const_mut(it),
+        ImplItem::Method(it) => v.visit_impl_item_method_mut(it),
+        ImplItem::Type(it) => v.visit_impl_item_type_mut(it),
+        ImplItem::Macro(it) => v.visit_impl_item_macro_mut(it),
+        ImplItem::Verbatim(_) => {}
+    }
+}
+
+pub fn visit_impl_item_method_mut<V>(v: &mut V, node: &mut ImplItemMethod)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_signature_mut(&mut node.sig);
+    v.visit_block_mut(&mut node.block);
+}
+
+pub fn visit_impl_item_type_mut<V>(v: &mut V, node: &mut ImplItemType)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generics_mut(&mut node.generics);
+    for it in &mut node.bounds {
+        v.visit_type_param_bound_mut(it);
+    }
+    if let Some((_, ty)) = &mut node.ty {
+        v.visit_type_mut(ty);
+    }
+    if let Some(it) = &mut node.trailing_where_clause {
+        v.visit_where_clause_mut(it);
+    }
+}
+
+pub fn visit_item_mut<V>(v: &mut V, node: &mut Item)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        Item::Impl(it) => v.visit_item_const_impl_mut(it),
+        Item::Trait(it) => v.visit_item_const_trait_mut(it),
+        Item::Fn(it) => v.visit_item_const_fn_mut(it),
+        Item::Mod(it) => v.visit_item_const_mod_mut(it),
+        Item::Other(it) => v.visit_syn_item_mut(it),
+    }
+}
+
+pub fn visit_item_const_fn_mut<V>(v: &mut V, node: &mut ItemConstFn)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_signature_mut(&mut node.sig);
+    v.visit_block_mut(&mut node.block);
+}
+
+pub fn visit_item_const_impl_mut<V>(v: &mut V, node: &mut ItemConstImpl)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_generics_mut(&mut node.generics);
+    if let Some((_, path, _)) = &mut node.trait_ {
+        v.visit_path_mut(path);
+    }
+    v.visit_type_mut(&mut node.self_ty);
+    for it in &mut node.items {
+        v.visit_impl_item_mut(it);
+    }
+}
+
+pub fn visit_item_const_mod_mut<V>(v: &mut V, node: &mut ItemConstMod)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    for it in &mut node.items {
+        v.visit_item_mut(it);
+    }
+}
+
+pub fn visit_item_const_trait_mut<V>(v: &mut V, node: &mut ItemConstTrait)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_visibility_mut(&mut node.vis);
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generics_mut(&mut node.generics);
+    for it in &mut node.supertraits {
+        v.visit_type_param_bound_mut(it);
+    }
+    for it in &mut node.items {
+        v.visit_trait_item_mut(it);
+    }
+}
+
+pub fn visit_predicate_type_mut<V>(v: &mut V, node: &mut PredicateType)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    if let Some(it) = &mut node.lifetimes {
+        v.visit_bound_lifetimes_mut(it);
+    }
+    v.visit_type_mut(&mut node.bounded_ty);
+    for it in &mut node.bounds {
+        v.visit_type_param_bound_mut(it);
+    }
+}
+
+pub fn visit_signature_mut<V>(v: &mut V, node: &mut Signature)
+where
+    V: VisitMut + ?Sized,
+{
+    if let Some(it) = &mut node.abi {
+        v.visit_abi_mut(it);
+    }
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generics_mut(&mut node.generics);
+    for it in &mut node.inputs {
+        v.visit_fn_arg_mut(it);
+    }
+    if let Some(it) = &mut node.variadic {
+        v.visit_variadic_mut(it);
+    }
+    v.visit_return_type_mut(&mut node.output);
+}
+
+pub fn visit_stmt_mut<V>(v: &mut V, node: &mut Stmt)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        Stmt::Item(it) => v.visit_item_mut(it),
+        Stmt::Other(it) => v.visit_syn_stmt_mut(it),
+    }
+}
+
+pub fn visit_tilde_const_mut<V>(v: &mut V, node: &mut TildeConst)
+where
+    V: VisitMut + ?Sized,
+{
+    let _ = (v, node);
+}
+
+pub fn visit_trait_bound_mut<V>(v: &mut V, node: &mut TraitBound)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_trait_bound_modifier_mut(&mut node.modifier);
+    if let Some(it) = &mut node.lifetimes {
+        v.visit_bound_lifetimes_mut(it);
+    }
+    v.visit_path_mut(&mut node.path);
+}
+
+pub fn visit_trait_bound_modifier_mut<V>(v: &mut V, node: &mut TraitBoundModifier)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        TraitBoundModifier::None => {}
+        TraitBoundModifier::Maybe(_) => {}
+        TraitBoundModifier::TildeConst(it) => v.visit_tilde_const_mut(it),
+        TraitBoundModifier::BracketConst(it) => v.visit_bracket_const_mut(it),
+        TraitBoundModifier::Const(_) => {}
+    }
+}
+
+pub fn visit_trait_item_mut<V>(v: &mut V, node: &mut TraitItem)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        TraitItem::Const(it) => v.visit_trait_item_const_mut(it),
+        TraitItem::Method(it) => v.visit_trait_item_method_mut(it),
+        TraitItem::Type(it) => v.visit_trait_item_type_mut(it),
+        TraitItem::Macro(it) => v.visit_trait_item_macro_mut(it),
+        TraitItem::Verbatim(_) => {}
+    }
+}
+
+pub fn visit_trait_item_method_mut<V>(v: &mut V, node: &mut TraitItemMethod)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_signature_mut(&mut node.sig);
+    if let Some(it) = &mut node.default {
+        v.visit_block_mut(it);
+    }
+}
+
+pub fn visit_trait_item_type_mut<V>(v: &mut V, node: &mut TraitItemType)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_ident_mut(&mut node.ident);
+    v.visit_generics_mut(&mut node.generics);
+    for it in &mut node.bounds {
+        v.visit_type_param_bound_mut(it);
+    }
+    if let Some((_, ty)) = &mut node.default {
+        v.visit_type_mut(ty);
+    }
+    if let Some(it) = &mut node.trailing_where_clause {
+        v.visit_where_clause_mut(it);
+    }
+}
+
+pub fn visit_type_param_mut<V>(v: &mut V, node: &mut TypeParam)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_attribute_mut(it);
+    }
+    v.visit_ident_mut(&mut node.ident);
+    for it in &mut node.bounds {
+        v.visit_type_param_bound_mut(it);
+    }
+    if let Some(it) = &mut node.default {
+        v.visit_type_mut(it);
+    }
+}
+
+pub fn visit_type_param_bound_mut<V>(v: &mut V, node: &mut TypeParamBound)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        TypeParamBound::Trait(it) => v.visit_trait_bound_mut(it),
+        TypeParamBound::Lifetime(it) => v.visit_lifetime_mut(it),
+    }
+}
+
+pub fn visit_where_clause_mut<V>(v: &mut V, node: &mut WhereClause)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.predicates {
+        v.visit_where_predicate_mut(it);
+    }
+}
+
+pub fn visit_where_predicate_mut<V>(v: &mut V, node: &mut WherePredicate)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        WherePredicate::Type(it) => v.visit_predicate_type_mut(it),
+        WherePredicate::Lifetime(it) => v.visit_predicate_lifetime_mut(it),
+        WherePredicate::Eq(it) => v.visit_predicate_eq_mut(it),
+    }
+}
@@ -0,0 +1,74 @@
+use quote::quote;
+use unconst_trait_impl_core::{expand_item_impl, unconst_item_fn, unconst_item_impl, Mode};
+
+#[test]
+fn unconsts_trait_impls() {
+    let unconsted = unconst_item_impl(quote! {
+        impl<T: ~const Default> const Trait for Wrapper<T> {
+            fn get() -> T {
+                T::default()
+            }
+        }
+    })
+    .unwrap();
+    let expected = quote! {
+        impl<T: Default> Trait for Wrapper<T> {
+            fn get() -> T {
+                T::default()
+            }
+        }
+    };
+    assert_eq!(unconsted.to_string(), expected.to_string());
+}
+
+#[test]
+fn unconsts_fns() {
+    let unconsted = unconst_item_fn(quote! {
+        pub const fn zeroed<T: ~const Default>() -> T {
+            T::default()
+        }
+    })
+    .unwrap();
+    let expected = quote! {
+        pub fn zeroed<T: Default>() -> T {
+            T::default()
+        }
+    };
+    assert_eq!(unconsted.to_string(), expected.to_string());
+}
+
+#[test]
+fn passes_input_through() {
+    let input = quote! {
+        impl<T: ~const Default> const Trait for Wrapper<T> {}
+    };
+    let passed = expand_item_impl(input.clone(), Mode::Passthrough)
+        .into_result()
+        .unwrap();
+    assert_eq!(passed.to_string(), input.to_string());
+}
+
+#[test]
+fn switches_on_cfg() {
+    let switched = unconst_item_impl(quote! {
+        cfg(feature = "nightly") =>
+        impl const Trait for Type {}
+    })
+    .unwrap();
+    let expected = quote! {
+        #[cfg(feature = "nightly")]
+        macro_rules! __unconst_trait_impl_emit {
+            ($($item:tt)*) => { $($item)* };
+        }
+        #[cfg(feature = "nightly")]
+        __unconst_trait_impl_emit! { impl const Trait for Type {} }
+        #[cfg(not(feature = "nightly"))]
+        impl Trait for Type {}
+    };
+    assert_eq!(switched.to_string(), expected.to_string());
+}
+
+#[test]
+fn rejects_other_items() {
+    assert!(unconst_item_impl(quote! { struct Type; }).is_err());
+}
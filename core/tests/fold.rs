@@ -0,0 +1,54 @@
+#![cfg(feature = "fold")]
+
+use quote::{quote, ToTokens};
+use syn::parse_quote;
+use unconst_trait_impl_core::{
+    fold::{self, Fold},
+    ItemConstImpl, TraitBoundModifier,
+};
+
+// Makes every bound a plain one and every trailing expression a `Default::default()`
+struct Plain;
+
+impl Fold for Plain {
+    fn fold_trait_bound_modifier(&mut self, modifier: TraitBoundModifier) -> TraitBoundModifier {
+        match fold::fold_trait_bound_modifier(self, modifier) {
+            TraitBoundModifier::Maybe(question) => TraitBoundModifier::Maybe(question),
+            _ => TraitBoundModifier::None,
+        }
+    }
+
+    fn fold_syn_stmt(&mut self, stmt: syn::Stmt) -> syn::Stmt {
+        match stmt {
+            syn::Stmt::Expr(_) => syn::Stmt::Expr(parse_quote!(Default::default())),
+            stmt => stmt,
+        }
+    }
+}
+
+#[test]
+fn rewrites_nested_statements() {
+    let item: ItemConstImpl = syn::parse2(quote! {
+        impl<T: ?Sized> const Trait for Wrapper<T> {
+            fn get<U: ~const Default>() -> U {
+                const fn helper<V: ~const Default>() -> V {
+                    V::default()
+                }
+                helper()
+            }
+        }
+    })
+    .unwrap();
+    let folded = Plain.fold_item_const_impl(item);
+    let expected = quote! {
+        impl<T: ?Sized> const Trait for Wrapper<T> {
+            fn get<U: Default>() -> U {
+                const fn helper<V: Default>() -> V {
+                    Default::default()
+                }
+                Default::default()
+            }
+        }
+    };
+    assert_eq!(folded.into_token_stream().to_string(), expected.to_string());
+}
@@ -0,0 +1,65 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use unconst_trait_impl_core::{expand_item_impl, Mode};
+
+fn warns(input: TokenStream2) -> bool {
+    let output = expand_item_impl(input, Mode::Unconst)
+        .into_result()
+        .unwrap();
+    output
+        .to_string()
+        .contains("unnecessary_unconst_trait_impl")
+}
+
+#[test]
+fn warns_about_impls_with_nothing_to_unconst() {
+    assert!(warns(quote! {
+        impl<T: Clone> Default for Wrapper<T> {
+            fn default() -> Self { todo!() }
+        }
+    }));
+    assert!(warns(quote! {
+        impl Wrapper {
+            const fn new() -> Self { Wrapper }
+        }
+    }));
+}
+
+#[test]
+fn doesnt_warn_about_impls_with_const_syntax() {
+    assert!(!warns(quote!(impl const Default for Wrapper {})));
+    assert!(!warns(quote!(
+        impl const Default for Wrapper {}
+    )));
+    assert!(!warns(quote!(
+        impl<T: [const] Clone> Default for Wrapper<T> {}
+    )));
+    assert!(!warns(quote!(
+        impl<T> Default for Wrapper<T> where T: const Clone {}
+    )));
+    assert!(!warns(quote! {
+        impl<T: Clone> Wrapper<T> {
+            const fn new() -> Self { todo!() }
+        }
+    }));
+    assert!(!warns(quote! {
+        impl Wrapper {
+            fn f() -> u8 { (const || 1)() }
+        }
+    }));
+    assert!(!warns(quote! {
+        #![unconst(msrv = "1.61")]
+        impl Wrapper {
+            fn f() -> u8 { const { 1 } }
+        }
+    }));
+}
+
+#[test]
+fn warns_about_inline_consts_that_the_msrv_allows() {
+    assert!(warns(quote! {
+        impl Wrapper {
+            fn f() -> u8 { const { 1 } }
+        }
+    }));
+}
@@ -0,0 +1,63 @@
+use quote::quote;
+use unconst_trait_impl_core::{expand_item_impl, expand_item_trait, expand_items, Mode};
+
+#[test]
+fn reports_every_error_and_expands_the_items_that_parse() {
+    let expansion = expand_items(
+        quote! {
+            impl const A for X {}
+            impl const B for Y { fn f(: u8) {} }
+            const fn g<T: ~const C>() -> T { let x = ; }
+            struct S;
+        },
+        Mode::Unconst,
+    );
+    let expected = quote! {
+        impl A for X {}
+        impl B for Y {}
+        fn g<T: C>() -> T { ::core::todo!() }
+        struct S;
+    };
+    assert_eq!(expansion.tokens.to_string(), expected.to_string());
+    assert_eq!(expansion.error.unwrap().into_iter().count(), 2);
+}
+
+#[test]
+fn recovers_within_impls_and_traits() {
+    let expansion = expand_item_impl(
+        quote! {
+            impl const A for X { fn f() {} fn g(: u8); fn h() -> u8 { 1 } }
+        },
+        Mode::Unconst,
+    );
+    let expected = quote! {
+        impl A for X { fn f() {} fn h() -> u8 { 1 } }
+    };
+    assert_eq!(expansion.tokens.to_string(), expected.to_string());
+    assert_eq!(expansion.error.unwrap().into_iter().count(), 1);
+
+    let expansion = expand_item_trait(
+        quote! {
+            const trait A { fn f(); fn g(: u8); fn h() -> u8 { let x = ; } }
+        },
+        Mode::Unconst,
+    );
+    let expected = quote! {
+        trait A { fn f(); fn h() -> u8 { ::core::todo!() } }
+    };
+    assert_eq!(expansion.tokens.to_string(), expected.to_string());
+    assert_eq!(expansion.error.unwrap().into_iter().count(), 2);
+}
+
+#[test]
+fn keeps_the_item_before_trailing_tokens() {
+    let expansion = expand_item_impl(quote!(impl const A for X {} struct S;), Mode::Unconst);
+    assert_eq!(
+        expansion.tokens.to_string(),
+        quote!(impl A for X {}).to_string()
+    );
+    assert_eq!(
+        expansion.error.unwrap().to_string(),
+        "unexpected tokens after the item"
+    );
+}
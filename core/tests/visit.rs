@@ -0,0 +1,38 @@
+#![cfg(feature = "visit")]
+
+use quote::quote;
+use unconst_trait_impl_core::{visit::Visit, ItemConstImpl, TraitBound};
+
+// Collects the traits of the const bounds, however deeply nested
+struct ConstBounds(Vec<String>);
+
+impl<'ast> Visit<'ast> for ConstBounds {
+    fn visit_trait_bound(&mut self, bound: &'ast TraitBound) {
+        if !matches!(
+            bound.modifier,
+            unconst_trait_impl_core::TraitBoundModifier::None
+        ) {
+            let path = &bound.path;
+            self.0.push(quote!(#path).to_string());
+        }
+    }
+}
+
+#[test]
+fn visits_nested_bounds() {
+    let item: ItemConstImpl = syn::parse2(quote! {
+        impl<T: ~const Clone> const Trait for Wrapper<T> {
+            fn get<U: [const] Default>() -> U
+            where
+                T: Copy,
+            {
+                const fn helper<V: const PartialEq>() {}
+                U::default()
+            }
+        }
+    })
+    .unwrap();
+    let mut bounds = ConstBounds(Vec::new());
+    bounds.visit_item_const_impl(&item);
+    assert_eq!(bounds.0, ["Clone", "Default", "PartialEq"]);
+}
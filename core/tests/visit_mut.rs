@@ -0,0 +1,46 @@
+#![cfg(feature = "visit-mut")]
+
+use quote::{quote, ToTokens};
+use syn::token::Bracket;
+use unconst_trait_impl_core::{
+    visit_mut::{self, VisitMut},
+    BracketConst, ItemConstImpl, TraitBoundModifier,
+};
+
+// Respells `~const Trait` as `[const] Trait`
+struct Respell;
+
+impl VisitMut for Respell {
+    fn visit_trait_bound_modifier_mut(&mut self, modifier: &mut TraitBoundModifier) {
+        if let TraitBoundModifier::TildeConst(tilde_const) = modifier {
+            *modifier = TraitBoundModifier::BracketConst(BracketConst {
+                bracket_token: Bracket(tilde_const.tilde.span),
+                const_: tilde_const.const_,
+            });
+        }
+        visit_mut::visit_trait_bound_modifier_mut(self, modifier);
+    }
+}
+
+#[test]
+fn rewrites_nested_bounds() {
+    let mut item: ItemConstImpl = syn::parse2(quote! {
+        impl<T: ~const Clone> const Trait for Wrapper<T> {
+            fn get<U: ~const Default>() -> U {
+                let keep = const |value: U| value;
+                keep(U::default())
+            }
+        }
+    })
+    .unwrap();
+    Respell.visit_item_const_impl_mut(&mut item);
+    let expected = quote! {
+        impl<T: [const] Clone> const Trait for Wrapper<T> {
+            fn get<U: [const] Default>() -> U {
+                let keep = const |value: U| value;
+                keep(U::default())
+            }
+        }
+    };
+    assert_eq!(item.into_token_stream().to_string(), expected.to_string());
+}
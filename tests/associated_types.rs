@@ -0,0 +1,31 @@
+use unconst_trait_impl::unconst_trait_impl;
+
+pub trait Container {
+    type Item: Default;
+    type Iter<'a, T: Clone>
+    where
+        T: Default + 'a;
+}
+
+pub struct Type;
+
+unconst_trait_impl! {
+    impl const Container for Type {
+        type Item: ~const Default = u8;
+        type Iter<'a, T: ~const Clone> = core::slice::Iter<'a, T> where T: ~const Default + 'a;
+    }
+}
+
+fn first<'a, C>(items: &'a [u8]) -> Option<&'a u8>
+where
+    C: Container<Iter<'a, u8> = core::slice::Iter<'a, u8>>,
+{
+    let mut iter: C::Iter<'a, u8> = items.iter();
+    iter.next()
+}
+
+#[test]
+fn associated_types() {
+    assert_eq!(<Type as Container>::Item::default(), 0);
+    assert_eq!(first::<Type>(&[1, 2]), Some(&1));
+}
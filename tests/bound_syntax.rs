@@ -0,0 +1,17 @@
+use unconst_trait_impl::unconst_trait_impl;
+
+#[derive(Debug, PartialEq)]
+pub struct Pair<T, U>(T, U);
+
+unconst_trait_impl! {
+    impl<T: [const] Default, U: const Default> const Default for Pair<T, U> {
+        fn default() -> Self {
+            Pair(T::default(), U::default())
+        }
+    }
+}
+
+#[test]
+fn bound_syntax() {
+    assert_eq!(Pair::<u8, bool>::default(), Pair(0, false));
+}
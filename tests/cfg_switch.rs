@@ -0,0 +1,60 @@
+use unconst_trait_impl::{unconst, unconst_trait_impl};
+
+pub trait Name {
+    fn name() -> &'static str;
+}
+
+pub struct Stable;
+pub struct Nightly;
+
+// The predicate doesn't hold, so the unconsted impl is used
+unconst_trait_impl! {
+    cfg(any()) =>
+    impl const Name for Stable {
+        fn name() -> &'static str {
+            "stable"
+        }
+    }
+}
+
+// The predicate holds, so the impl is used as written. Stable has to accept it, so that
+// the macro warns about it having nothing to unconst
+#[allow(deprecated)]
+mod holds {
+    use super::{unconst_trait_impl, Name, Nightly};
+
+    unconst_trait_impl! {
+        cfg(all()) =>
+        impl Name for Nightly {
+            fn name() -> &'static str {
+                "nightly"
+            }
+        }
+    }
+}
+
+mod nested {
+    pub struct Both;
+
+    super::unconst! {
+        cfg(any()) =>
+        impl const super::Name for Both {
+            fn name() -> &'static str {
+                "both"
+            }
+        }
+        impl const Default for Both {
+            fn default() -> Self {
+                Both
+            }
+        }
+    }
+}
+
+#[test]
+fn cfg_switch() {
+    assert_eq!(Stable::name(), "stable");
+    assert_eq!(Nightly::name(), "nightly");
+    assert_eq!(<nested::Both as Name>::name(), "both");
+    let _: nested::Both = Default::default();
+}
@@ -0,0 +1,12 @@
+use unconst_trait_impl::unconst_fn;
+
+unconst_fn! {
+    pub const fn zeroed<T: ~const Default>() -> T {
+        T::default()
+    }
+}
+
+#[test]
+fn const_fn() {
+    assert_eq!(zeroed::<u8>(), 0);
+}
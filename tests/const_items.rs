@@ -0,0 +1,21 @@
+use unconst_trait_impl::{unconst_trait, unconst_trait_impl};
+
+unconst_trait! {
+    pub const trait Zeroed: [const] Default {
+        fn zeroed() -> Self {
+            Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Wrapper<T>(T);
+
+unconst_trait_impl! {
+    const impl<T: [const] Default> Zeroed for Wrapper<T> {}
+}
+
+#[test]
+fn const_items() {
+    assert_eq!(Wrapper::<u8>::zeroed(), Wrapper(0));
+}
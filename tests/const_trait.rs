@@ -0,0 +1,20 @@
+use unconst_trait_impl::unconst_trait;
+
+unconst_trait! {
+    #[const_trait]
+    pub trait Zeroed: ~const Default {
+        fn zeroed<U: ~const Default>() -> U {
+            U::default()
+        }
+    }
+}
+
+#[derive(Default)]
+struct Unit;
+
+impl Zeroed for Unit {}
+
+#[test]
+fn const_trait() {
+    assert_eq!(Unit::zeroed::<u8>(), 0);
+}
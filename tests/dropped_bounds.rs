@@ -0,0 +1,52 @@
+use unconst_trait_impl::unconst_trait_impl;
+
+mod nightly {
+    // Only the bounds dropped on stable refer to it
+    #[allow(dead_code)]
+    pub trait Marker {}
+}
+
+mod stable {
+    pub trait Marker {}
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Wrapper<T>(T);
+
+pub trait Trait {
+    fn get(self) -> u8;
+}
+
+// Neither `Wrapper<u8>: Drop` nor `u8: nightly::Marker` holds, so the impl only
+// applies if those bounds are dropped
+unconst_trait_impl! {
+    #![unconst(drop_bounds(nightly::Marker))]
+    impl<T: ~const Into<u8> + ~const nightly::Marker> const Trait for Wrapper<T>
+    where
+        Self: ~const Drop,
+    {
+        fn get(self) -> u8 {
+            self.0.into()
+        }
+    }
+}
+
+impl stable::Marker for u8 {}
+
+// `stable::Marker` isn't the listed path, so its bound becomes a plain one
+unconst_trait_impl! {
+    #![unconst(drop_bounds(nightly::Marker))]
+    impl<T: ~const Clone + ~const stable::Marker> const Clone for Wrapper<T> {
+        fn clone(&self) -> Self {
+            Wrapper(self.0.clone())
+        }
+    }
+}
+
+fn is_clone<T: Clone>(_: &T) {}
+
+#[test]
+fn dropped_bounds() {
+    assert_eq!(Wrapper(1u8).get(), 1);
+    is_clone(&Wrapper(1u8));
+}
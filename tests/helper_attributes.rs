@@ -0,0 +1,40 @@
+use unconst_trait_impl::unconst_trait_impl;
+
+#[derive(Clone, Copy)]
+pub struct Wrapper<T>(T);
+
+unconst_trait_impl! {
+    #![unconst(msrv = "1.61")]
+    impl<#[unconst(drop)] T: ~const Default + Copy> Wrapper<T> {
+        #[unconst(keep)]
+        pub const fn get(&self) -> T {
+            self.0
+        }
+
+        #[unconst(drop)]
+        pub const fn reset(&mut self) {
+            self.0 = T::default()
+        }
+
+        #[unconst(skip)]
+        pub fn inner(&self) -> &T {
+            &self.0
+        }
+    }
+}
+
+// Only compiles if the `reset` above was dropped
+impl<T> Wrapper<T> {
+    pub fn reset(&mut self) {}
+}
+
+// Only compiles if `get` stayed const
+const VALUE: u8 = Wrapper(7u8).get();
+
+#[test]
+fn helper_attributes() {
+    assert_eq!(VALUE, 7);
+    let mut wrapper = Wrapper(1u8);
+    wrapper.reset();
+    assert_eq!(*wrapper.inner(), 1);
+}
@@ -0,0 +1,20 @@
+use unconst_trait_impl::unconst_fn;
+
+unconst_fn! {
+    pub const fn wrap(value: impl ~const Into<u8>) -> Box<dyn ~const Fn() -> u8> {
+        let value = value.into();
+        Box::new(move || value)
+    }
+}
+
+unconst_fn! {
+    pub const fn double(value: u8) -> impl [const] Copy + Into<u16> {
+        value * 2
+    }
+}
+
+#[test]
+fn impl_trait_types() {
+    assert_eq!(wrap(true)(), 1);
+    assert_eq!(double(2).into(), 4u16);
+}
@@ -0,0 +1,36 @@
+use core::ops::Add;
+use unconst_trait_impl::unconst_trait_impl;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Matrix<T>(T);
+
+unconst_trait_impl! {
+    impl<T: ~const Add<Output = T> + Copy> Matrix<T> {
+        pub const fn add(self, other: Self) -> Self {
+            Matrix(self.0 + other.0)
+        }
+    }
+}
+
+struct Unit;
+
+unconst_trait_impl! {
+    impl Unit {
+        pub const fn size<T: ?Sized>(_: &T) -> u8 {
+            0
+        }
+        pub const fn sum<T: ~const Add<Output = T>>(a: T, b: T) -> T {
+            a + b
+        }
+    }
+}
+
+// Only compiles if `size` stays const
+const SIZE: u8 = Unit::size("");
+
+#[test]
+fn inherent_impl() {
+    assert_eq!(Matrix(1).add(Matrix(2)), Matrix(3));
+    assert_eq!(Unit::sum(1, 2), 3);
+    assert_eq!(SIZE, 0);
+}
@@ -0,0 +1,31 @@
+use core::marker::PhantomData;
+use unconst_trait_impl::unconst;
+
+pub struct Zst<T: ?Sized>(PhantomData<T>);
+
+unconst! {
+    #[const_trait]
+    pub trait TraitName {}
+
+    impl<T: ?Sized> const TraitName for Zst<T> {}
+
+    pub const fn zst<T: ~const TraitName + ?Sized>() -> Zst<T> {
+        Zst(PhantomData)
+    }
+
+    mod defaults {
+        use super::*;
+
+        impl<T: ~const TraitName + ?Sized> const Default for Zst<T> {
+            fn default() -> Self {
+                Zst(PhantomData)
+            }
+        }
+    }
+}
+
+#[test]
+fn items() {
+    let _: Zst<Zst<str>> = zst();
+    let _: Zst<Zst<str>> = Default::default();
+}
@@ -0,0 +1,27 @@
+use unconst_trait_impl::unconst_trait_impl;
+
+#[derive(Clone, Copy)]
+pub struct Wrapper<T>(T);
+
+unconst_trait_impl! {
+    #![unconst(msrv = "1.61")]
+    impl<T: Copy> Wrapper<T> {
+        pub const fn new(value: T) -> Self {
+            Wrapper(value)
+        }
+
+        pub const fn default_with<U: ~const Default>(value: T) -> (Self, U) {
+            (Wrapper(value), U::default())
+        }
+    }
+}
+
+// Only compiles if `new` stayed const
+const WRAPPED: Wrapper<u8> = Wrapper::new(7);
+
+#[test]
+fn msrv() {
+    assert_eq!(WRAPPED.0, 7);
+    let (wrapper, default) = Wrapper::default_with::<u8>(1);
+    assert_eq!((wrapper.0, default), (1, 0));
+}
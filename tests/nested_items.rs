@@ -0,0 +1,36 @@
+use unconst_trait_impl::unconst_trait_impl;
+
+pub trait Trait {
+    fn count(&self) -> u8;
+}
+
+pub struct Type;
+
+unconst_trait_impl! {
+    impl const Trait for Type {
+        fn count(&self) -> u8 {
+            struct Local;
+
+            impl const Trait for Local {
+                fn count(&self) -> u8 {
+                    2
+                }
+            }
+
+            const fn same<T: ~const PartialEq>(a: T, b: T) -> bool {
+                a == b
+            }
+
+            if same(Local.count(), 2) {
+                Local.count() + 1
+            } else {
+                0
+            }
+        }
+    }
+}
+
+#[test]
+fn nested_items() {
+    assert_eq!(Type.count(), 3);
+}
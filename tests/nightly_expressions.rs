@@ -0,0 +1,44 @@
+use unconst_trait_impl::unconst_trait_impl;
+
+pub trait Trait {
+    fn apply(&self, value: u8) -> u8;
+    fn empty(&self) -> [Vec<u8>; 2];
+}
+
+pub struct Type;
+
+unconst_trait_impl! {
+    impl const Trait for Type {
+        fn apply(&self, value: u8) -> u8 {
+            let add = const |x: u8| x + 1;
+            let double = const move || value * 2;
+            add(double())
+        }
+
+        fn empty(&self) -> [Vec<u8>; 2] {
+            [const { Vec::new() }; 2]
+        }
+    }
+}
+
+pub struct Old;
+
+unconst_trait_impl! {
+    #![unconst(msrv = "1.61")]
+    impl const Trait for Old {
+        fn apply(&self, value: u8) -> u8 {
+            value + const { 1 + 1 }
+        }
+
+        fn empty(&self) -> [Vec<u8>; 2] {
+            [Vec::new(), Vec::new()]
+        }
+    }
+}
+
+#[test]
+fn nightly_expressions() {
+    assert_eq!(Type.apply(2), 5);
+    assert_eq!(Type.empty(), [Vec::new(), Vec::new()]);
+    assert_eq!(Old.apply(2), 4);
+}
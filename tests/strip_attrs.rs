@@ -0,0 +1,28 @@
+use unconst_trait_impl::{unconst_trait, unconst_trait_impl};
+
+unconst_trait! {
+    #![unconst(strip_attrs(rustc_const_unstable))]
+    #[const_trait]
+    pub trait Zero {
+        #[rustc_const_unstable(feature = "const_zero", issue = "none")]
+        fn zero() -> Self;
+    }
+}
+
+pub struct Wrapper(u8);
+
+// Stable rejects the `rustc_` attributes, so the impl only compiles if they are stripped
+unconst_trait_impl! {
+    #![unconst(strip_attrs(rustc_const_unstable))]
+    #[rustc_const_unstable(feature = "const_zero", issue = "none")]
+    impl const Zero for Wrapper {
+        fn zero() -> Self {
+            Wrapper(0)
+        }
+    }
+}
+
+#[test]
+fn strip_attrs() {
+    assert_eq!(Wrapper::zero().0, 0);
+}
@@ -0,0 +1,20 @@
+use unconst_trait_impl::unconst_trait_impl;
+
+pub trait Trait {
+    fn get(&self) -> u8;
+}
+
+pub struct Type;
+
+unconst_trait_impl! {
+    impl const Trait for Type {
+        const fn get(&self) -> u8 {
+            1
+        }
+    }
+}
+
+#[test]
+fn trait_impl_methods() {
+    assert_eq!(Type.get(), 1);
+}